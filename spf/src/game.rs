@@ -1,9 +1,20 @@
+pub mod calibration;
+pub mod commands;
+pub mod depth_chart;
 pub mod engine;
 pub mod environment;
+pub mod error;
 pub mod events;
 pub mod fac;
+pub mod gamelog;
 pub mod kickoff_play;
+pub mod lineup_report;
+pub mod seats;
+pub mod simulation;
+pub mod solitaire;
 pub mod standard_play;
+#[cfg(test)]
+pub(crate) mod testutils;
 
 // The data model, loaders and stat primitives now live in the shared `spf_core`
 // crate. Re-export the pieces the server references under the `game` namespace so
@@ -12,12 +23,14 @@ pub mod standard_play;
 pub use spf_core::{lineup, players, stats};
 
 use std::{
+    collections::{HashMap, VecDeque},
     fs::{self, File},
     io::{BufWriter, Write},
 };
 
 use engine::defs::GAMECONSTANTS;
 use serde::{Deserialize, Serialize};
+use spf_core::persist::TeamData;
 use tokio::sync::broadcast;
 use utoipa::ToSchema;
 
@@ -27,11 +40,16 @@ use self::{
         PlayResult, PlayType, Yard,
     },
     environment::GameEnvironment,
-    events::GameEvent,
-    fac::FacManager,
+    error::GameError,
+    events::{GameEvent, SequencedEvent},
+    fac::{FacData, FacManager},
+    gamelog::{GameLog, LogEntry},
     kickoff_play::KickoffPlay,
-    players::{Roster, TeamID},
-    standard_play::StandardPlay,
+    lineup::OffensiveBox,
+    players::{Player, Roster, TeamID},
+    seats::{Seat, SeatTokens},
+    solitaire::Solitaire,
+    standard_play::{StandardPlay, Validatable},
 };
 
 /// Error returned by [`Game::create_game`] when the requested teams can't be resolved.
@@ -41,7 +59,7 @@ pub enum CreateGameError {
     UnknownTeam(TeamID),
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
 pub enum GameTeams {
     Home,
     Away,
@@ -55,7 +73,42 @@ impl GameTeams {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+/// Which Statis Pro rule set a game is played under.
+///
+/// `Basic` switches off the optional mechanics (extra coverage, draw/play-action, pass-rush
+/// line ratings) so new players can learn the core game; `Advanced` is the full game and the
+/// default.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, ToSchema)]
+pub enum RulesLevel {
+    Basic,
+    #[default]
+    Advanced,
+}
+
+impl RulesLevel {
+    pub fn is_advanced(&self) -> bool {
+        *self == RulesLevel::Advanced
+    }
+}
+
+/// Per-game settings chosen when the game is created.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct GameOptions {
+    #[serde(default)]
+    pub rules: RulesLevel,
+    /// The team whose calls are made by the engine from the FAC `solitaire` column, if any.
+    #[serde(default)]
+    pub solitaire: Option<GameTeams>,
+    /// Keep each side's call out of events and `GET /offense|defense/call` until the play is
+    /// run, so neither coach sees the other's call early.
+    #[serde(default)]
+    pub hide_calls: bool,
+    /// Play at a neutral site: both teams use their road big-play rating.
+    #[serde(default)]
+    pub neutral_site: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
 pub enum GamePlayStatus {
     Touchdown,
     Safety,
@@ -75,7 +128,7 @@ pub enum GameStatus {
     ExtraPoint,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct GameState {
     pub last_status: GamePlayStatus,
     pub quarter: i32,
@@ -87,6 +140,13 @@ pub struct GameState {
     pub home_score: i32,
     pub away_score: i32,
     pub play_counter: u32,
+    #[serde(default)]
+    pub rules: RulesLevel,
+    #[serde(default)]
+    pub neutral_site: bool,
+    /// The offense has had its big-play chance this drive.
+    #[serde(default)]
+    pub big_play_tried: bool,
 }
 
 impl GameState {
@@ -102,13 +162,31 @@ impl GameState {
             home_score: 0,
             away_score: 0,
             play_counter: 0,
+            rules: RulesLevel::default(),
+            neutral_site: false,
+            big_play_tried: false,
         };
     }
 
+    /// Yards to the first-down marker (or the goal line, if that is closer).
+    pub fn yards_to_go(&self) -> Yard {
+        self.first_down_target - self.yard_line
+    }
+
+    /// Yards to the opponent's goal line.
+    pub fn yards_to_goal(&self) -> Yard {
+        100 - self.yard_line
+    }
+
+    /// Regulation is over: the clock has run out in the final quarter.
+    pub fn is_game_over(&self) -> bool {
+        self.quarter >= GAMECONSTANTS.quarters && self.time_remaining <= 0
+    }
+
     pub fn get_next_move_types(&self) -> Vec<PlayType> {
         match self.last_status {
             GamePlayStatus::Touchdown => vec![PlayType::ExtraPoint],
-            GamePlayStatus::Safety => vec![PlayType::Punt],
+            GamePlayStatus::Safety => vec![PlayType::Kickoff],
             GamePlayStatus::FieldGoal => vec![PlayType::Kickoff],
             GamePlayStatus::PossessionChange | GamePlayStatus::Ongoing => {
                 vec![PlayType::Standard, PlayType::Punt, PlayType::FieldGoal]
@@ -121,7 +199,7 @@ impl GameState {
     pub fn get_next_move_default(&self) -> PlayType {
         match self.last_status {
             GamePlayStatus::Touchdown => PlayType::ExtraPoint,
-            GamePlayStatus::Safety => PlayType::Punt,
+            GamePlayStatus::Safety => PlayType::Kickoff,
             GamePlayStatus::FieldGoal => PlayType::Kickoff,
             GamePlayStatus::PossessionChange => PlayType::Standard,
             GamePlayStatus::Ongoing => PlayType::Standard,
@@ -165,6 +243,13 @@ pub struct PlayAndState {
     pub new_state: GameState,
 }
 
+/// Whether a side has called the upcoming play, and the call itself unless it is hidden.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallStatus<C> {
+    pub set: bool,
+    pub call: Option<C>,
+}
+
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct PlayTypeInfo {
     pub allowed_types: Vec<PlayType>,
@@ -177,6 +262,29 @@ pub struct PlayTypeInfo {
 /// docs/design/ws-events-architecture.md §3.
 const GAME_EVENT_CHANNEL_CAPACITY: usize = 128;
 
+/// How many past events a game keeps for clients catching up after a reconnect or lag
+/// (`?since=<seq>`). Several plays' worth; older gaps fall back to a state snapshot.
+const GAME_EVENT_HISTORY: usize = 1024;
+
+/// Everything `run_current_play` changes irreversibly, captured when a play runs (the deck
+/// as of the end of the previous play) so [`Game::undo`] can put the game back where it was.
+#[derive(Clone)]
+struct PlaySnapshot {
+    state: GameState,
+    fac_deck: FacManager,
+    play_type: PlayType,
+    offlineup: Option<OffenseIDLineup>,
+    deflineup: Option<DefenseIDLineup>,
+}
+
+/// The lineup each team last had accepted for each play type. The next play of that type
+/// starts with it, so coaches only send the changes.
+#[derive(Clone, Default)]
+struct LastLineups {
+    offense: HashMap<(GameTeams, PlayType), OffenseIDLineup>,
+    defense: HashMap<(GameTeams, PlayType), DefenseIDLineup>,
+}
+
 #[derive(Serialize)]
 pub struct Game {
     #[serde(skip_serializing)]
@@ -190,26 +298,60 @@ pub struct Game {
     // pub next_play: Box<dyn PlayImpl + Send>,
     offlineup: Option<OffenseIDLineup>,
     deflineup: Option<DefenseIDLineup>,
+    #[serde(skip_serializing)]
+    offcall: Option<OffenseCall>,
+    #[serde(skip_serializing)]
+    defcall: Option<DefenseCall>,
+    #[serde(skip_serializing)]
+    last_lineups: LastLineups,
+    options: GameOptions,
 
     #[serde(skip_serializing)]
     pub fac_deck: FacManager,
 
+    /// One snapshot per entry in `past_plays`, taken before that play ran.
+    #[serde(skip_serializing)]
+    snapshots: Vec<PlaySnapshot>,
+
+    /// The deck as the previous play left it, so cards drawn while setting up the next play
+    /// (e.g. solitaire calls) are rolled back with it.
+    #[serde(skip_serializing)]
+    deck_at_play_start: FacManager,
+
+    /// Every command applied to this game; see [`gamelog`].
+    #[serde(skip_serializing)]
+    log: GameLog,
+
+    /// Who may act on and see what; see [`seats`].
+    #[serde(skip_serializing)]
+    seats: SeatTokens,
+
     /// Runtime plumbing: broadcasts domain events to transport adapters. Not game data,
     /// so it is skipped in serialization.
     #[serde(skip_serializing)]
-    event_tx: broadcast::Sender<GameEvent>,
+    event_tx: broadcast::Sender<SequencedEvent>,
+
+    /// The most recent events, oldest first, for catch-up.
+    #[serde(skip_serializing)]
+    history: VecDeque<SequencedEvent>,
+
+    /// Sequence number of the last event emitted; 0 before the first.
+    #[serde(skip_serializing)]
+    last_seq: u64,
 }
 
 impl Game {
     /// Create a game from the shared [`GameEnvironment`] and the two teams' ids.
     ///
     /// Resolves each team against the environment's league (moving the membership check out
-    /// of the HTTP layer), then builds the game with its own cloned FAC deck. The environment
-    /// is only borrowed, so one environment can back many games.
+    /// of the HTTP layer), then builds the game with its own cloned FAC deck, seeded so the
+    /// game can be replayed from its log. The environment is only borrowed, so one
+    /// environment can back many games.
     pub fn create_game(
         env: &GameEnvironment,
         home: &TeamID,
         away: &TeamID,
+        options: GameOptions,
     ) -> Result<Self, CreateGameError> {
         let home_roster = env
             .roster(home)
@@ -219,30 +361,51 @@ impl Game {
             .ok_or_else(|| CreateGameError::UnknownTeam(away.clone()))?;
 
         Ok(Self::build(
-            home_roster.clone(),
-            away_roster.clone(),
-            env.new_deck(),
+            home_roster,
+            away_roster,
+            env.new_deck().with_seed(rand::random()),
+            options,
         ))
     }
 
     /// Pure dependency-injected constructor: builds a game from already-resolved rosters and
     /// an owned FAC deck. No disk access. This is the seam tests use to inject a deterministic
     /// deck (see `docs/design/testing-strategy.md` §5).
-    fn build(home: Roster, away: Roster, fac_deck: FacManager) -> Self {
+    fn build(home: Roster, away: Roster, fac_deck: FacManager, options: GameOptions) -> Self {
         let start_type = PlayType::Kickoff;
         let (event_tx, _rx) = broadcast::channel(GAME_EVENT_CHANNEL_CAPACITY);
 
-        let game = Self {
+        let mut game = Self {
             home,
             away,
-            state: GameState::start_state(),
+            state: GameState {
+                rules: options.rules,
+                neutral_site: options.neutral_site,
+                ..GameState::start_state()
+            },
             past_plays: vec![],
             next_play: Some(start_type.create_impl()),
             offlineup: None,
             deflineup: None,
+            offcall: None,
+            defcall: None,
+            last_lineups: LastLineups::default(),
+            options,
+            deck_at_play_start: fac_deck.clone(),
             fac_deck,
+            snapshots: vec![],
+            log: GameLog::default(),
+            seats: SeatTokens::generate(),
             event_tx,
+            history: VecDeque::new(),
+            last_seq: 0,
         };
+        game.log.record(LogEntry::Created {
+            home: TeamData::from_roster(&game.home),
+            away: TeamData::from_roster(&game.away),
+            seed: game.fac_deck.seed(),
+            options: game.options.clone(),
+        });
 
         // No subscribers exist yet at creation (see docs/plans/ws-events-stage2.md D2); this
         // is a deliberate no-op today but preserves the "every mutation emits" invariant.
@@ -251,18 +414,63 @@ impl Game {
         game
     }
 
-    /// Publish a domain event to all current subscribers. A send error means "no subscribers
-    /// right now", which is normal and intentionally ignored (architecture §4).
-    fn emit(&self, event: GameEvent) {
+    /// Number a domain event, keep it in the catch-up history and publish it to all current
+    /// subscribers. A send error means "no subscribers right now", which is normal and
+    /// intentionally ignored (architecture §4).
+    fn emit(&mut self, event: GameEvent) {
+        self.last_seq += 1;
+        let event = SequencedEvent {
+            seq: self.last_seq,
+            event,
+        };
+        if self.history.len() == GAME_EVENT_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(event.clone());
         let _ = self.event_tx.send(event);
     }
 
     /// Obtain a receiver for this game's event stream. Each transport adapter (e.g. the WS
     /// handler) calls this to get its own independent receiver.
-    pub fn subscribe(&self) -> broadcast::Receiver<GameEvent> {
+    pub fn subscribe(&self) -> broadcast::Receiver<SequencedEvent> {
         self.event_tx.subscribe()
     }
 
+    /// Sequence number of the latest event, i.e. what a snapshot taken now is current to.
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+
+    /// The events after `seq`, oldest first, or `None` when some of them have already
+    /// dropped out of the history (the caller should resync from a snapshot instead).
+    pub fn events_since(&self, seq: u64) -> Option<Vec<SequencedEvent>> {
+        let oldest = self.history.front().map_or(self.last_seq + 1, |e| e.seq);
+        if seq + 1 < oldest {
+            return None;
+        }
+        Some(
+            self.history
+                .iter()
+                .filter(|e| e.seq > seq)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// What a (re)connecting subscriber needs before following the live stream: the events
+    /// after `since` when they are all still in the history, otherwise a `GameStarted`-shaped
+    /// snapshot of the current state numbered with [`last_seq`](Self::last_seq).
+    pub fn resync(&self, since: Option<u64>) -> Vec<SequencedEvent> {
+        since
+            .and_then(|seq| self.events_since(seq))
+            .unwrap_or_else(|| {
+                vec![SequencedEvent {
+                    seq: self.last_seq,
+                    event: GameEvent::GameStarted { state: self.state },
+                }]
+            })
+    }
+
     fn get_current_off_roster(&self) -> &Roster {
         match self.state.possession {
             GameTeams::Away => &self.away,
@@ -280,16 +488,21 @@ impl Game {
     pub fn set_offensive_lineup_from_ids(
         &mut self,
         id_lineup: &OffenseIDLineup,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         let r = self.get_current_off_roster().clone();
         self.next_play
             .as_mut()
-            .ok_or("No Play Set")?
+            .ok_or(GameError::NoPlaySet)?
             .set_offense_lineup(id_lineup, &r)?;
         self.offlineup = Some(id_lineup.clone());
-        self.emit(GameEvent::OffensiveLineupSet {
+        self.remember_offensive_lineup();
+        self.log.record(LogEntry::OffensiveLineupSet {
             lineup: id_lineup.clone(),
         });
+        self.emit(GameEvent::OffensiveLineupSet {
+            team: self.state.possession,
+            lineup: Some(id_lineup.clone()),
+        });
 
         Ok(())
     }
@@ -297,42 +510,148 @@ impl Game {
     pub fn set_defensive_lineup_from_ids(
         &mut self,
         id_lineup: &DefenseIDLineup,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         let r = self.get_current_def_roster().clone();
 
         self.next_play
             .as_mut()
-            .ok_or("No Play Set")?
+            .ok_or(GameError::NoPlaySet)?
             .set_defense_lineup(id_lineup, &r)?;
         self.deflineup = Some(id_lineup.clone());
-        self.emit(GameEvent::DefensiveLineupSet {
+        self.remember_defensive_lineup();
+        self.log.record(LogEntry::DefensiveLineupSet {
             lineup: id_lineup.clone(),
         });
+        self.emit(GameEvent::DefensiveLineupSet {
+            team: self.state.possession.other_team(),
+            lineup: Some(id_lineup.clone()),
+        });
 
         Ok(())
     }
 
-    pub fn set_offense_call(&mut self, off_call: OffenseCall) -> Result<(), String> {
+    pub fn set_offense_call(&mut self, off_call: OffenseCall) -> Result<(), GameError> {
+        self.check_qb_endurance(&off_call)?;
+        if let Some(call) = off_call.as_standard_offense_call() {
+            call.check_rules(self.state.rules)?;
+        }
         self.next_play
             .as_mut()
-            .ok_or("No Play Set")?
-            .set_offense_call(off_call)
+            .ok_or(GameError::NoPlaySet)?
+            .set_offense_call(off_call.clone())?;
+        self.log.record(LogEntry::OffenseCallSet {
+            call: off_call.clone(),
+        });
+        self.emit(GameEvent::OffenseCallSet {
+            team: self.state.possession,
+            call: Some(off_call.clone()),
+        });
+        self.offcall = Some(off_call);
+        Ok(())
     }
 
-    pub fn set_defense_call(&mut self, def_call: DefenseCall) -> Result<(), String> {
+    pub fn set_defense_call(&mut self, def_call: DefenseCall) -> Result<(), GameError> {
+        if let Some(call) = def_call.as_standard_defense_call() {
+            call.check_rules(self.state.rules)?;
+        }
         self.next_play
             .as_mut()
-            .ok_or("No Play Set")?
-            .set_defense_call(def_call)
+            .ok_or(GameError::NoPlaySet)?
+            .set_defense_call(def_call.clone())?;
+        self.log.record(LogEntry::DefenseCallSet {
+            call: def_call.clone(),
+        });
+        self.emit(GameEvent::DefenseCallSet {
+            team: self.state.possession.other_team(),
+            call: Some(def_call.clone()),
+        });
+        self.defcall = Some(def_call);
+        Ok(())
+    }
+
+    /// The offense's call for the upcoming play, as `seat` may see it (see [`seats`]).
+    pub fn get_offense_call(&self, seat: Seat) -> CallStatus<OffenseCall> {
+        let visible = self.view(seat).can_see(self.state.possession);
+        CallStatus {
+            set: self.offcall.is_some(),
+            call: self.offcall.clone().filter(|_| visible),
+        }
+    }
+
+    /// The defense's call for the upcoming play, as `seat` may see it.
+    pub fn get_defense_call(&self, seat: Seat) -> CallStatus<DefenseCall> {
+        let visible = self.view(seat).can_see(self.state.possession.other_team());
+        CallStatus {
+            set: self.defcall.is_some(),
+            call: self.defcall.clone().filter(|_| visible),
+        }
+    }
+
+    /// A designed quarterback run needs a rested quarterback: after each one he sits out
+    /// `endurance_rushing` of his team's standard plays.
+    fn check_qb_endurance(&self, call: &OffenseCall) -> Result<(), GameError> {
+        if !call
+            .as_standard_offense_call()
+            .is_some_and(|c| c.is_designed_qb_run())
+        {
+            return Ok(());
+        }
+        // Without a quarterback in the lineup the call's own checks refuse the play.
+        let qb = match self.next_play.as_ref().map(|play| play.get_play()) {
+            Some(Play::StandardPlay(play)) => play
+                .offense
+                .as_ref()
+                .and_then(|o| o.get_player_in_pos(&OffensiveBox::QB))
+                .and_then(|qb| Player::is_qb(qb.get_full_player())),
+            _ => None,
+        };
+        let Some(qb) = qb else {
+            return Ok(());
+        };
+
+        let offense = self.state.possession;
+        let rested = self
+            .snapshots
+            .iter()
+            .zip(&self.past_plays)
+            .rev()
+            .filter_map(|(snapshot, past)| match &past.play {
+                Play::StandardPlay(play) if snapshot.state.possession == offense => Some(play),
+                _ => None,
+            })
+            .position(|play| {
+                play.offense_call
+                    .as_ref()
+                    .is_some_and(|c| c.is_designed_qb_run())
+                    && play.qb_id().as_ref() == Some(&qb.id)
+            });
+        match rested {
+            Some(rested) if (rested as i32) < qb.endurance_rushing => {
+                Err(GameError::InvalidCall(format!(
+                    "{} can't run again for {} more plays",
+                    qb.name,
+                    qb.endurance_rushing - rested as i32
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Draw a FAC outside of a play (e.g. to pick a call), skipping Z cards. Logged so a
+    /// replay draws the same cards.
+    pub(crate) fn draw_card(&mut self) -> Result<FacData, GameError> {
+        let card = Solitaire::draw_card(&mut self.fac_deck).map_err(GameError::Engine)?;
+        self.log.record(LogEntry::CardDrawn);
+        Ok(card)
     }
 
     // unused: duplicate of the used `set_offensive_lineup_from_ids`; kept pending removal.
     #[allow(dead_code)]
-    pub fn set_offense_lineup(&mut self, off_id: OffenseIDLineup) -> Result<(), String> {
+    pub fn set_offense_lineup(&mut self, off_id: OffenseIDLineup) -> Result<(), GameError> {
         let r = self.get_current_off_roster().clone();
         self.next_play
             .as_mut()
-            .ok_or("No Play Set")?
+            .ok_or(GameError::NoPlaySet)?
             .set_offense_lineup(&off_id, &r)?;
         self.offlineup = Some(off_id);
         Ok(())
@@ -340,49 +659,264 @@ impl Game {
 
     // unused: duplicate of the used `set_defensive_lineup_from_ids`; kept pending removal.
     #[allow(dead_code)]
-    pub fn set_defense_lineup(&mut self, def_id: DefenseIDLineup) -> Result<(), String> {
+    pub fn set_defense_lineup(&mut self, def_id: DefenseIDLineup) -> Result<(), GameError> {
         let r = self.get_current_def_roster().clone();
 
         self.next_play
             .as_mut()
-            .ok_or("No Play Set")?
+            .ok_or(GameError::NoPlaySet)?
             .set_defense_lineup(&def_id, &r)?;
         self.deflineup = Some(def_id);
         Ok(())
     }
 
-    pub fn get_offensive_lineup_ids(&self) -> &Option<OffenseIDLineup> {
-        &self.offlineup
+    /// The offense's lineup for the upcoming play, if set and visible to `seat`.
+    pub fn get_offensive_lineup_ids(&self, seat: Seat) -> Option<&OffenseIDLineup> {
+        let visible = self.view(seat).can_see(self.state.possession);
+        self.offlineup.as_ref().filter(|_| visible)
     }
 
-    pub fn get_defensive_lineup_ids(&self) -> &Option<DefenseIDLineup> {
-        &self.deflineup
+    /// The defense's lineup for the upcoming play, if set and visible to `seat`.
+    pub fn get_defensive_lineup_ids(&self, seat: Seat) -> Option<&DefenseIDLineup> {
+        let visible = self.view(seat).can_see(self.state.possession.other_team());
+        self.deflineup.as_ref().filter(|_| visible)
     }
 
-    pub fn run_current_play(&mut self) -> Result<PlayAndState, String> {
-        // Increment play counter in state
+    pub fn run_current_play(&mut self) -> Result<PlayAndState, GameError> {
+        // The lineup may have changed since the call was made.
+        if let Some(call) = &self.offcall {
+            self.check_qb_endurance(call)?;
+        }
+        let snapshot = PlaySnapshot {
+            state: self.state,
+            fac_deck: self.deck_at_play_start.clone(),
+            play_type: self
+                .next_play
+                .as_ref()
+                .ok_or(GameError::NoPlaySet)?
+                .get_type(),
+            offlineup: self.offlineup.clone(),
+            deflineup: self.deflineup.clone(),
+        };
+        self.apply_solitaire_call()?;
 
         let res = run_play(
             &self.state,
             &mut self.fac_deck,
-            self.next_play.as_ref().ok_or("No Play Set")?,
+            self.next_play.as_ref().ok_or(GameError::NoPlaySet)?,
         )?;
 
         self.past_plays.push(res.clone());
+        let snapshot_state = snapshot.state;
+        self.snapshots.push(snapshot);
+        self.deck_at_play_start = self.fac_deck.clone();
+        self.log.record(LogEntry::PlayRun {
+            new_state: res.new_state,
+        });
 
         // Update state, ensuring play counter is preserved
         self.state = GameState { ..res.new_state };
-        self.set_next_play_type(self.state.get_next_move_default())?; // emits NextPlayTypeSet
+        self.change_play_type(self.state.get_next_move_default())?; // emits NextPlayTypeSet
 
         // Then announce the play itself. Net emission order for one play is
-        // NextPlayTypeSet -> PlayRun (see docs/plans/ws-events-stage2.md D3).
+        // NextPlayTypeSet -> PlayRun (see docs/plans/ws-events-stage2.md D3), followed by
+        // any lineups carried over to the next play.
         self.emit(GameEvent::PlayRun {
             play: Box::new(res.clone()),
         });
+        self.carry_lineups();
+        if self.state.is_game_over() && !snapshot_state.is_game_over() {
+            self.emit(GameEvent::GameEnded { state: self.state });
+        }
 
         return Ok(res);
     }
 
+    /// Roll the game back to just before play `to` (a `play_counter` value) ran, or before the
+    /// last play when `to` is `None`. State, FAC deck and lineups are restored; the calls are
+    /// cleared so the coaches call the play again. Returns the restored state.
+    pub fn undo(&mut self, to: Option<u32>) -> Result<GameState, GameError> {
+        let index = match to {
+            None => self
+                .snapshots
+                .len()
+                .checked_sub(1)
+                .ok_or(GameError::NoSuchPlay(None))?,
+            Some(counter) => self
+                .snapshots
+                .iter()
+                .position(|s| s.state.play_counter == counter)
+                .ok_or(GameError::NoSuchPlay(Some(counter)))?,
+        };
+        let undone = (self.past_plays.len() - index) as u32;
+        self.snapshots.truncate(index + 1);
+        self.past_plays.truncate(index);
+        let snapshot = self.snapshots.pop().ok_or(GameError::NoSuchPlay(to))?;
+        self.log.record(LogEntry::PlayUndone { to });
+
+        self.state = snapshot.state;
+        self.deck_at_play_start = snapshot.fac_deck.clone();
+        self.fac_deck = snapshot.fac_deck;
+        self.next_play = Some(snapshot.play_type.create_impl());
+        self.offlineup = None;
+        self.deflineup = None;
+        self.offcall = None;
+        self.defcall = None;
+        // The lineups were legal when the play ran against the same rosters, so these only
+        // fail if the play type no longer takes a lineup; leave it unset in that case.
+        if let Some(lineup) = snapshot.offlineup {
+            if self.reapply_offensive_lineup(lineup) {
+                self.remember_offensive_lineup();
+            }
+        }
+        if let Some(lineup) = snapshot.deflineup {
+            if self.reapply_defensive_lineup(lineup) {
+                self.remember_defensive_lineup();
+            }
+        }
+
+        self.emit(GameEvent::PlayUndone {
+            state: self.state,
+            undone,
+            play_type: snapshot.play_type,
+        });
+        Ok(self.state)
+    }
+
+    /// Put `lineup` on the pending play without logging it, if the play takes it.
+    fn reapply_offensive_lineup(&mut self, lineup: OffenseIDLineup) -> bool {
+        let roster = self.get_current_off_roster().clone();
+        let accepted = self
+            .next_play
+            .as_mut()
+            .is_some_and(|play| play.set_offense_lineup(&lineup, &roster).is_ok());
+        if accepted {
+            self.offlineup = Some(lineup);
+        }
+        accepted
+    }
+
+    fn reapply_defensive_lineup(&mut self, lineup: DefenseIDLineup) -> bool {
+        let roster = self.get_current_def_roster().clone();
+        let accepted = self
+            .next_play
+            .as_mut()
+            .is_some_and(|play| play.set_defense_lineup(&lineup, &roster).is_ok());
+        if accepted {
+            self.deflineup = Some(lineup);
+        }
+        accepted
+    }
+
+    fn remember_offensive_lineup(&mut self) {
+        if let (Some(play), Some(lineup)) = (&self.next_play, &self.offlineup) {
+            let key = (self.state.possession, play.get_type());
+            self.last_lineups.offense.insert(key, lineup.clone());
+        }
+    }
+
+    fn remember_defensive_lineup(&mut self) {
+        if let (Some(play), Some(lineup)) = (&self.next_play, &self.deflineup) {
+            let key = (self.state.possession.other_team(), play.get_type());
+            self.last_lineups.defense.insert(key, lineup.clone());
+        }
+    }
+
+    /// Start the pending play with the lineups its teams last had accepted for a play of
+    /// its type, if they still pass the play's checks, and announce them like lineups the
+    /// coaches set. Not logged: a replay carries the same lineups over.
+    fn carry_lineups(&mut self) {
+        let play_type = match &self.next_play {
+            Some(play) => play.get_type(),
+            None => return,
+        };
+        let offense = self.state.possession;
+        let defense = offense.other_team();
+        if let Some(lineup) = self
+            .last_lineups
+            .offense
+            .get(&(offense, play_type))
+            .cloned()
+        {
+            if self.reapply_offensive_lineup(lineup.clone()) {
+                self.emit(GameEvent::OffensiveLineupSet {
+                    team: offense,
+                    lineup: Some(lineup),
+                });
+            }
+        }
+        if let Some(lineup) = self
+            .last_lineups
+            .defense
+            .get(&(defense, play_type))
+            .cloned()
+        {
+            if self.reapply_defensive_lineup(lineup.clone()) {
+                self.emit(GameEvent::DefensiveLineupSet {
+                    team: defense,
+                    lineup: Some(lineup),
+                });
+            }
+        }
+    }
+
+    /// When one side is engine-controlled, draw a FAC and set that side's call for the
+    /// upcoming standard play from it. A call already made for that side stands, and other
+    /// play types are left to the caller. The other side's half of the play is checked
+    /// first, so a play that can't run doesn't use up a card; the draw and the call are
+    /// logged like anyone else's, so a replay makes the same call without drawing for it.
+    fn apply_solitaire_call(&mut self) -> Result<(), GameError> {
+        let side = match self.options.solitaire {
+            Some(side) => side,
+            None => return Ok(()),
+        };
+        let called = if side == self.state.possession {
+            self.offcall.is_some()
+        } else {
+            self.defcall.is_some()
+        };
+        if called {
+            return Ok(());
+        }
+        let play = match self
+            .next_play
+            .as_ref()
+            .ok_or(GameError::NoPlaySet)?
+            .get_play()
+        {
+            Play::StandardPlay(p) => p,
+            _ => return Ok(()),
+        };
+
+        if play.offense.is_none() || play.defense.is_none() {
+            return Err(GameError::NotReady(
+                "Set both lineups before the play".to_string(),
+            ));
+        }
+        if side == self.state.possession {
+            play.defense_call
+                .as_ref()
+                .ok_or_else(|| GameError::NotReady("Defense Call  not set".to_string()))?
+                .validate(&play, &self.state)?;
+        } else {
+            play.offense_call
+                .as_ref()
+                .ok_or_else(|| GameError::NotReady("Offense Call  not set".to_string()))?
+                .validate(&play, &self.state)?;
+        }
+
+        let card = self.draw_card()?;
+        if side == self.state.possession {
+            let call =
+                Solitaire::offense_call(&card, &self.state, &play).map_err(GameError::Engine)?;
+            self.set_offense_call(OffenseCall::StandardOffenseCall(call))
+        } else {
+            let call =
+                Solitaire::defense_call(&card, &self.state, &play).map_err(GameError::Engine)?;
+            self.set_defense_call(DefenseCall::StandardDefenseCall(call))
+        }
+    }
+
     // unused: abandoned stub (ignores its args and returns a fresh start_state); kept pending removal.
     #[allow(dead_code)]
     fn gen_new_state(
@@ -400,17 +934,27 @@ impl Game {
         }
     }
 
-    pub fn set_next_play_type(&mut self, playtype: PlayType) -> Result<(), String> {
+    pub fn set_next_play_type(&mut self, playtype: PlayType) -> Result<(), GameError> {
+        self.change_play_type(playtype)?;
+        self.log.record(LogEntry::NextPlayTypeSet {
+            play_type: playtype,
+        });
+        self.carry_lineups();
+        Ok(())
+    }
+
+    /// Select the next play type without logging it, for changes that follow from another
+    /// logged command (running a play picks the default next type).
+    fn change_play_type(&mut self, playtype: PlayType) -> Result<(), GameError> {
         let allowed = self.state.get_next_move_types();
         if !allowed.contains(&playtype) {
-            return Err(format!("Valid plays are {:?}", allowed));
+            return Err(GameError::IllegalPlayType { allowed });
         }
-        let same_type = self.next_play.as_ref().unwrap().get_type() == playtype;
         self.next_play = Some(playtype.create_impl());
-        if !same_type {
-            self.offlineup = None;
-            self.deflineup = None;
-        }
+        self.offlineup = None;
+        self.deflineup = None;
+        self.offcall = None;
+        self.defcall = None;
         self.emit(GameEvent::NextPlayTypeSet {
             play_type: playtype,
         });
@@ -460,6 +1004,10 @@ impl Game {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::{
+        seats::SeatAction,
+        standard_play::{DefensivePlay, DefensiveStrategy},
+    };
     use spf_core::players::{Player, Roster, TeamID};
 
     fn empty_roster(name: &str) -> Roster {
@@ -480,6 +1028,7 @@ mod tests {
             empty_roster("Home"),
             empty_roster("Away"),
             fac::FacManager::from_cards(vec![]),
+            GameOptions::default(),
         )
     }
 
@@ -499,7 +1048,7 @@ mod tests {
             .expect("Kickoff is a legal next play type from the start state");
 
         // Assert: the event arrived and carries the right PlayType (PlayType: PartialEq).
-        match rx.try_recv() {
+        match rx.try_recv().map(|e| e.event) {
             Ok(GameEvent::NextPlayTypeSet { play_type }) => {
                 assert_eq!(play_type, PlayType::Kickoff);
             }
@@ -507,6 +1056,168 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_events_are_numbered_and_replayable_since_a_seq() {
+        let mut game = game_with_injected_deck();
+        let start = game.last_seq();
+        game.set_next_play_type(PlayType::Kickoff).unwrap();
+        game.set_offense_call(kickoff_call()).unwrap();
+        assert_eq!(game.last_seq(), start + 2);
+
+        let missed = game.events_since(start).expect("still in the history");
+        assert_eq!(
+            missed.iter().map(|e| e.seq).collect::<Vec<_>>(),
+            vec![start + 1, start + 2]
+        );
+        assert!(game.events_since(game.last_seq()).unwrap().is_empty());
+        assert_eq!(game.resync(Some(start)).len(), 2);
+    }
+
+    #[test]
+    fn test_resync_falls_back_to_a_snapshot_once_history_rolls_over() {
+        let mut game = game_with_injected_deck();
+        for _ in 0..GAME_EVENT_HISTORY + 1 {
+            game.set_next_play_type(PlayType::Kickoff).unwrap();
+        }
+        assert!(game.events_since(0).is_none());
+
+        let resync = game.resync(Some(0));
+        assert_eq!(resync.len(), 1);
+        assert_eq!(resync[0].seq, game.last_seq());
+        assert!(matches!(resync[0].event, GameEvent::GameStarted { .. }));
+    }
+
+    fn kickoff_call() -> OffenseCall {
+        OffenseCall::KickoffOffenseCall(engine::KickoffOffenseCall {
+            kind: engine::KickoffKind::Normal,
+        })
+    }
+
+    #[test]
+    fn test_offense_call_is_shown_and_cleared_with_the_play() {
+        let mut game = game_with_injected_deck();
+        let mut rx = game.subscribe();
+        // The away team kicks off the start state.
+        assert!(!game.get_offense_call(Seat::Away).set);
+
+        game.set_offense_call(kickoff_call()).unwrap();
+        match rx.try_recv().map(|e| e.event) {
+            Ok(GameEvent::OffenseCallSet { team, call }) => {
+                assert_eq!(team, GameTeams::Away);
+                assert!(call.is_some());
+            }
+            other => panic!("expected OffenseCallSet, got {:?}", other),
+        }
+        let status = game.get_offense_call(Seat::Away);
+        assert!(status.set && status.call.is_some());
+        let status = game.get_offense_call(Seat::Home);
+        assert!(
+            status.set && status.call.is_none(),
+            "hidden from the defense"
+        );
+
+        game.set_next_play_type(PlayType::Kickoff).unwrap();
+        assert!(
+            !game.get_offense_call(Seat::Away).set,
+            "a new play starts uncalled"
+        );
+    }
+
+    #[test]
+    fn test_hidden_calls_keep_spectators_out() {
+        let mut game = Game::build(
+            empty_roster("Home"),
+            empty_roster("Away"),
+            fac::FacManager::from_cards(vec![]),
+            GameOptions {
+                hide_calls: true,
+                ..GameOptions::default()
+            },
+        );
+        game.set_offense_call(kickoff_call()).unwrap();
+
+        let status = game.get_offense_call(Seat::Spectator);
+        assert!(status.set);
+        assert!(status.call.is_none());
+        assert!(game.get_offense_call(Seat::Away).call.is_some());
+    }
+
+    #[test]
+    fn test_only_the_team_in_possession_runs_the_offense() {
+        let game = game_with_injected_deck();
+        assert!(game.authorize(Seat::Away, SeatAction::Offense).is_ok());
+        assert!(game.authorize(Seat::Home, SeatAction::Offense).is_err());
+        assert!(game.authorize(Seat::Home, SeatAction::Defense).is_ok());
+        assert!(game.authorize(Seat::Spectator, SeatAction::Coach).is_err());
+        let token = game.seat_tokens().home.clone();
+        assert_eq!(game.seat_for(&token), Some(Seat::Home));
+    }
+
+    #[test]
+    fn test_undo_needs_a_play_to_take_back() {
+        let mut game = game_with_injected_deck();
+        assert!(game.undo(None).is_err());
+        assert!(game.undo(Some(0)).is_err());
+        assert_eq!(game.state.play_counter, 0);
+    }
+
+    #[test]
+    fn test_formations_must_exist_and_be_legal() {
+        let mut game = game_with_injected_deck();
+        game.next_play = Some(PlayType::Standard.create_impl());
+        let pick: lineup::FormationLineup =
+            serde_json::from_value(serde_json::json!({"name": "I-form"})).unwrap();
+        let err = game.set_offensive_lineup_from_formation(&pick).unwrap_err();
+        assert_eq!(err.code(), "unknown_formation");
+
+        // Nobody is on an empty roster, so nothing can be saved to its depth chart.
+        let lineup: lineup::StandardIDOffenseLineup =
+            serde_json::from_value(serde_json::json!({"qb": "QB-1"})).unwrap();
+        let err = game
+            .save_offense_formation(GameTeams::Away, "I-form".into(), lineup)
+            .unwrap_err();
+        assert_eq!(err.code(), "unknown_player");
+        assert!(game.depth_chart(GameTeams::Away).is_empty());
+    }
+
+    #[test]
+    fn test_lineup_check_reports_the_strategys_formations() {
+        let mut game = game_with_injected_deck();
+        game.next_play = Some(PlayType::Standard.create_impl());
+        // Four linemen, four linebackers and two backs: a row short for 4-4 or 3-5.
+        let lineup: DefenseIDLineup = serde_json::from_value(serde_json::json!({
+            "box_a": ["DL-1"], "box_b": ["DL-2"], "box_c": [], "box_d": ["DL-3"],
+            "box_e": ["DL-4"], "box_f": "LB-1", "box_g": "LB-2", "box_h": "LB-3",
+            "box_i": "LB-4", "box_k": "DB-1", "box_l": [], "box_m": "DB-2",
+        }))
+        .unwrap();
+
+        let report = game
+            .check_defensive_lineup(&lineup, Some(DefensiveStrategy::DoubleCover))
+            .unwrap();
+        assert!(!report.legal);
+        assert_eq!(report.formations.len(), 2);
+        let formation: Vec<&str> = report
+            .problems
+            .iter()
+            .filter(|p| p.code == "illegal_formation")
+            .map(|p| p.message.as_str())
+            .collect();
+        assert_eq!(
+            formation,
+            vec![
+                "Improper secondary size: Expected 3-3 but was 2",
+                "Row2: 4 Row3: 2 - illegal lineup for DoubleCover",
+            ]
+        );
+        // Nothing is on these empty rosters, and nothing was set.
+        assert_eq!(report.problems.len(), 12);
+        assert!(game.deflineup.is_none());
+
+        let any = game.check_defensive_lineup(&lineup, None).unwrap();
+        assert_eq!(any.formations.len(), 4);
+    }
+
     fn team_id(name: &str) -> TeamID {
         TeamID {
             name: name.into(),
@@ -523,8 +1234,13 @@ mod tests {
     #[test]
     fn test_create_game_resolves_known_teams() {
         let env = env_with_teams(&["Home", "Away"]);
-        let game = Game::create_game(&env, &team_id("Home"), &team_id("Away"))
-            .expect("both teams are in the league");
+        let game = Game::create_game(
+            &env,
+            &team_id("Home"),
+            &team_id("Away"),
+            GameOptions::default(),
+        )
+        .expect("both teams are in the league");
         assert_eq!(game.home.get_team_name().name, "Home");
         assert_eq!(game.away.get_team_name().name, "Away");
     }
@@ -532,7 +1248,12 @@ mod tests {
     #[test]
     fn test_create_game_unknown_home_team() {
         let env = env_with_teams(&["Away"]);
-        match Game::create_game(&env, &team_id("Nope"), &team_id("Away")) {
+        match Game::create_game(
+            &env,
+            &team_id("Nope"),
+            &team_id("Away"),
+            GameOptions::default(),
+        ) {
             Err(CreateGameError::UnknownTeam(t)) => assert_eq!(t.name, "Nope"),
             Ok(_) => panic!("expected UnknownTeam(Nope), got Ok(game)"),
         }
@@ -541,9 +1262,189 @@ mod tests {
     #[test]
     fn test_create_game_unknown_away_team() {
         let env = env_with_teams(&["Home"]);
-        match Game::create_game(&env, &team_id("Home"), &team_id("Nope")) {
+        match Game::create_game(
+            &env,
+            &team_id("Home"),
+            &team_id("Nope"),
+            GameOptions::default(),
+        ) {
             Err(CreateGameError::UnknownTeam(t)) => assert_eq!(t.name, "Nope"),
             Ok(_) => panic!("expected UnknownTeam(Nope), got Ok(game)"),
         }
     }
+
+    #[test]
+    fn test_create_game_applies_rules_level() {
+        let env = env_with_teams(&["Home", "Away"]);
+        let options = GameOptions {
+            rules: RulesLevel::Basic,
+            ..GameOptions::default()
+        };
+        let game = Game::create_game(&env, &team_id("Home"), &team_id("Away"), options)
+            .expect("both teams are in the league");
+        assert_eq!(game.state.rules, RulesLevel::Basic);
+    }
+
+    #[test]
+    fn test_create_game_applies_neutral_site() {
+        let env = env_with_teams(&["Home", "Away"]);
+        let options = GameOptions {
+            neutral_site: true,
+            ..GameOptions::default()
+        };
+        let game = Game::create_game(&env, &team_id("Home"), &team_id("Away"), options)
+            .expect("both teams are in the league");
+        assert!(game.state.neutral_site);
+    }
+
+    #[test]
+    fn test_default_options_play_advanced_rules() {
+        let game = game_with_injected_deck();
+        assert!(game.state.rules.is_advanced());
+    }
+
+    #[test]
+    fn test_quarterback_rests_after_a_designed_run() {
+        let mut game = testutils::game(vec![testutils::run_card(1, 11)], GameOptions::default());
+        testutils::kick_off_to_home(&mut game);
+        let call = |target| {
+            OffenseCall::StandardOffenseCall(testutils::offense_call(
+                standard_play::OffensivePlayType::IL,
+                target,
+            ))
+        };
+
+        testutils::run_standard(&mut game, call(OffensiveBox::QB));
+        assert_eq!(game.state.possession, GameTeams::Home);
+
+        // The lineups carry over, so the same quarterback is behind center.
+        let err = game.set_offense_call(call(OffensiveBox::QB)).unwrap_err();
+        assert_eq!(err.code(), "invalid_call");
+        assert!(err.to_string().contains("can't run again"), "{}", err);
+
+        // Handing off is still fine.
+        assert!(game.set_offense_call(call(OffensiveBox::B1)).is_ok());
+    }
+
+    #[test]
+    fn test_undo_rewinds_the_deck_and_lineups() {
+        let mut game = testutils::game(testutils::drive_cards(), GameOptions::default());
+        testutils::kick_off_to_home(&mut game);
+        let handoff = |game: &mut Game| {
+            testutils::run_standard(game, testutils::handoff())
+                .result
+                .result
+        };
+        assert_eq!(handoff(&mut game), 8);
+        let first: Vec<i32> = (0..3).map(|_| handoff(&mut game)).collect();
+        assert_eq!(first, vec![6, 8, 6]);
+
+        let restored = game.undo(Some(2)).expect("play 2 can be rewound to");
+        assert_eq!(restored.play_counter, 2);
+        assert_eq!(game.past_plays.len(), 2);
+        assert!(game.offlineup.is_some(), "lineups survive");
+        assert!(game.offcall.is_none(), "the play is called again");
+
+        let replayed: Vec<i32> = (0..3).map(|_| handoff(&mut game)).collect();
+        assert_eq!(first, replayed, "the restored deck deals the same cards");
+    }
+
+    #[test]
+    fn test_lineups_carry_over_and_take_changes() {
+        let mut game = testutils::game(testutils::drive_cards(), GameOptions::default());
+        testutils::kick_off_to_home(&mut game);
+        testutils::run_standard(&mut game, testutils::handoff());
+        assert!(game.offcall.is_none());
+        let qb = |game: &Game| {
+            serde_json::to_value(game.offlineup.as_ref().expect("lineup carried over")).unwrap()
+                ["qb"]
+                .clone()
+        };
+        assert_eq!(qb(&game), serde_json::json!("QB-1"));
+        assert!(game.deflineup.is_some());
+
+        let changes: lineup::LineupChanges =
+            serde_json::from_value(serde_json::json!({"players": {"QB-1": "QB-2"}})).unwrap();
+        game.change_offensive_lineup(&changes).unwrap();
+        assert_eq!(qb(&game), serde_json::json!("QB-2"));
+
+        let changes: lineup::LineupChanges =
+            serde_json::from_value(serde_json::json!({"boxes": {"qb": null}})).unwrap();
+        let err = game.change_offensive_lineup(&changes).unwrap_err();
+        assert_eq!(err.code(), "missing_player");
+        assert_eq!(
+            qb(&game),
+            serde_json::json!("QB-2"),
+            "a refused change keeps the lineup"
+        );
+
+        // Picking the play type again clears the call but brings back the changed lineup.
+        game.set_next_play_type(PlayType::Standard).unwrap();
+        assert!(game.offcall.is_none());
+        assert_eq!(qb(&game), serde_json::json!("QB-2"));
+    }
+
+    #[test]
+    fn test_solitaire_side_only_calls_what_was_left_open() {
+        let mut game = testutils::game(
+            testutils::solitaire_cards(),
+            GameOptions {
+                solitaire: Some(GameTeams::Away),
+                ..GameOptions::default()
+            },
+        );
+        testutils::kick_off_to_home(&mut game);
+        let defense = |played: &PlayAndState| match &played.play {
+            Play::StandardPlay(p) => p.defense_call.as_ref().unwrap().defense_type,
+            _ => panic!("expected a standard play"),
+        };
+
+        // Left open, the engine calls the away defense off the card.
+        game.set_offense_call(testutils::handoff()).unwrap();
+        let played = game.run_current_play().unwrap();
+        assert_eq!(defense(&played), DefensivePlay::RunDefense);
+
+        // Made by hand, the call stands.
+        game.set_offense_call(testutils::handoff()).unwrap();
+        game.set_defense_call(DefenseCall::StandardDefenseCall(testutils::defense_call(
+            DefensivePlay::PassDefense,
+        )))
+        .unwrap();
+        let played = game.run_current_play().unwrap();
+        assert_eq!(defense(&played), DefensivePlay::PassDefense);
+    }
+
+    #[test]
+    fn test_basic_rules_refuse_advanced_calls_when_made() {
+        let mut game = testutils::game(
+            testutils::drive_cards(),
+            GameOptions {
+                rules: RulesLevel::Basic,
+                ..GameOptions::default()
+            },
+        );
+        testutils::kick_off_to_home(&mut game);
+        let logged = game.log().len();
+
+        let draw = OffenseCall::StandardOffenseCall(standard_play::StandardOffenseCall {
+            strategy: standard_play::OffensiveStrategy::Draw,
+            ..testutils::offense_call(
+                standard_play::OffensivePlayType::IL,
+                lineup::OffensiveBox::B1,
+            )
+        });
+        let err = game.set_offense_call(draw).unwrap_err();
+        assert_eq!(err.code(), "invalid_call");
+        assert!(game.offcall.is_none());
+
+        let double = DefenseCall::StandardDefenseCall(standard_play::StandardDefenseCall {
+            strategy: DefensiveStrategy::DoubleCover,
+            ..testutils::defense_call(DefensivePlay::PassDefense)
+        });
+        let err = game.set_defense_call(double).unwrap_err();
+        assert_eq!(err.code(), "invalid_call");
+        assert!(game.defcall.is_none());
+
+        assert_eq!(game.log().len(), logged);
+    }
 }
@@ -0,0 +1,396 @@
+//! Calibration: simulate a season's worth of games for every team and compare the engine's
+//! rates with real season totals, so mis-tuned tables show up as numbers instead of hunches.
+//!
+//! The reference is a CSV with one row per team (empty `player`) or player:
+//!
+//! ```text
+//! team,player,games,points,rush_att,rush_yds,pass_att,pass_cmp,pass_yds,pass_int,sacked,rec,rec_yds
+//! Chicago,,16,311,...
+//! Chicago,Walter Payton,16,,314,1421,...
+//! ```
+//!
+//! Any stat column may be left empty; metrics that need it are skipped for that row. Totals
+//! on both sides are normalized per game (or per attempt) before comparing. `points` is read
+//! but not compared until the simulation has a kicking game (see [`super::simulation`]).
+//!
+//! No reference file ships with the repo. Build one from the season's pages at
+//! Pro-Football-Reference (<https://www.pro-football-reference.com/years/1983/>): the team
+//! offense table for team rows, and the rushing, passing and receiving tables for players,
+//! with team and player names spelled as in the converted league data.
+
+use std::{collections::BTreeMap, fs::File, io::Read};
+
+use csv::ReaderBuilder;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    environment::GameEnvironment,
+    players::TeamID,
+    simulation::{simulate_game, PlayerStats},
+    GameOptions,
+};
+
+/// Season totals for a team or one of its players, as read from the reference file or
+/// accumulated from simulated games.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SeasonLine {
+    pub team: String,
+    /// Empty for a team's totals.
+    #[serde(default)]
+    pub player: String,
+    pub games: u32,
+    pub points: Option<i32>,
+    pub rush_att: Option<u32>,
+    pub rush_yds: Option<i32>,
+    pub pass_att: Option<u32>,
+    pub pass_cmp: Option<u32>,
+    pub pass_yds: Option<i32>,
+    pub pass_int: Option<u32>,
+    pub sacked: Option<u32>,
+    pub rec: Option<u32>,
+    pub rec_yds: Option<i32>,
+}
+
+impl SeasonLine {
+    fn simulated(team: &str, player: &str) -> Self {
+        Self {
+            team: team.to_string(),
+            player: player.to_string(),
+            games: 0,
+            points: Some(0),
+            rush_att: Some(0),
+            rush_yds: Some(0),
+            pass_att: Some(0),
+            pass_cmp: Some(0),
+            pass_yds: Some(0),
+            pass_int: Some(0),
+            sacked: Some(0),
+            rec: Some(0),
+            rec_yds: Some(0),
+        }
+    }
+
+    fn add_stats(&mut self, stats: &PlayerStats) {
+        fn add<T: std::ops::AddAssign + Copy>(total: &mut Option<T>, val: T) {
+            if let Some(t) = total.as_mut() {
+                *t += val;
+            }
+        }
+        add(&mut self.rush_att, stats.rush_att);
+        add(&mut self.rush_yds, stats.rush_yds);
+        add(&mut self.pass_att, stats.pass_att);
+        add(&mut self.pass_cmp, stats.pass_cmp);
+        add(&mut self.pass_yds, stats.pass_yds);
+        add(&mut self.pass_int, stats.pass_int);
+        add(&mut self.sacked, stats.sacked);
+        add(&mut self.rec, stats.rec);
+        add(&mut self.rec_yds, stats.rec_yds);
+    }
+
+    /// The compared metrics, each `None` when a stat it needs is missing or its denominator
+    /// is zero.
+    fn metrics(&self) -> Vec<(&'static str, Option<f64>)> {
+        let per_game = |v: Option<f64>| v.filter(|_| self.games > 0).map(|v| v / self.games as f64);
+        let ratio = |n: Option<f64>, d: Option<f64>| match (n, d) {
+            (Some(n), Some(d)) if d > 0.0 => Some(n / d),
+            _ => None,
+        };
+        let f = |v: Option<u32>| v.map(|v| v as f64);
+        let fi = |v: Option<i32>| v.map(|v| v as f64);
+        let dropbacks = match (self.pass_att, self.sacked) {
+            (Some(a), Some(s)) => Some((a + s) as f64),
+            _ => None,
+        };
+
+        // No points per game: without field goals and extra points, simulated scores come in
+        // sixes and would only measure that.
+        vec![
+            ("rush_att_per_game", per_game(f(self.rush_att))),
+            ("rush_yds_per_game", per_game(fi(self.rush_yds))),
+            ("yds_per_carry", ratio(fi(self.rush_yds), f(self.rush_att))),
+            ("pass_att_per_game", per_game(f(self.pass_att))),
+            ("cmp_pct", ratio(f(self.pass_cmp), f(self.pass_att))),
+            ("pass_yds_per_game", per_game(fi(self.pass_yds))),
+            (
+                "yds_per_pass_att",
+                ratio(fi(self.pass_yds), f(self.pass_att)),
+            ),
+            ("int_rate", ratio(f(self.pass_int), f(self.pass_att))),
+            ("sack_rate", ratio(f(self.sacked), dropbacks)),
+            ("rec_per_game", per_game(f(self.rec))),
+            ("rec_yds_per_game", per_game(fi(self.rec_yds))),
+        ]
+    }
+}
+
+/// One compared metric for a team or player.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CalibrationRow {
+    pub team: String,
+    pub player: String,
+    pub metric: String,
+    pub simulated: f64,
+    pub actual: f64,
+    /// `simulated - actual`.
+    pub diff: f64,
+    /// `diff` as a percentage of `actual`; `None` when `actual` is zero.
+    pub pct_diff: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationReport {
+    /// Games simulated per team (each game counts for both of its teams).
+    pub games_per_team: u32,
+    pub seed: u64,
+    pub rows: Vec<CalibrationRow>,
+    /// Reference rows with no simulated counterpart (unknown team or player name).
+    pub unmatched: Vec<String>,
+}
+
+impl CalibrationReport {
+    pub fn to_csv(&self) -> Result<String, String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        for row in &self.rows {
+            writer.serialize(row).map_err(|e| e.to_string())?;
+        }
+        let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+}
+
+/// Load reference season totals from a CSV file (format in the module docs).
+pub fn read_reference(path: &str) -> Result<Vec<SeasonLine>, String> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+    parse_reference(file)
+}
+
+fn parse_reference<R: Read>(reader: R) -> Result<Vec<SeasonLine>, String> {
+    ReaderBuilder::new()
+        .has_headers(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader)
+        .deserialize()
+        .collect::<Result<Vec<SeasonLine>, csv::Error>>()
+        .map_err(|e| e.to_string())
+}
+
+/// A schedule where every team plays exactly `games` games, half of them at home. Each
+/// round, every team hosts the team a fixed number of places after it in the league (the
+/// offset moving on each round, so the whole league comes around); an odd game count adds
+/// one round pairing each team with the one halfway round the league, which takes an even
+/// number of teams.
+fn schedule(teams: &[TeamID], games: u32) -> Result<Vec<(TeamID, TeamID)>, String> {
+    let n = teams.len();
+    if n < 2 {
+        return Ok(vec![]);
+    }
+    if games % 2 == 1 && n % 2 == 1 {
+        return Err(format!(
+            "{} teams can't each play an odd number of games ({})",
+            n, games
+        ));
+    }
+
+    let mut schedule: Vec<(TeamID, TeamID)> = (0..games as usize / 2)
+        .flat_map(|round| {
+            let offset = 1 + round % (n - 1);
+            (0..n).map(move |i| (teams[i].clone(), teams[(i + offset) % n].clone()))
+        })
+        .collect();
+    if games % 2 == 1 {
+        schedule.extend((0..n / 2).map(|i| (teams[i].clone(), teams[i + n / 2].clone())));
+    }
+    Ok(schedule)
+}
+
+/// Simulate `games_per_team` games for every team in the league and compare the results
+/// with `reference`. Game `n` of the schedule is shuffled from `seed + n`. An odd
+/// `games_per_team` needs a league with an even number of teams.
+pub fn calibrate(
+    env: &GameEnvironment,
+    reference: &[SeasonLine],
+    games_per_team: u32,
+    seed: u64,
+    options: GameOptions,
+) -> Result<CalibrationReport, String> {
+    let mut teams: Vec<TeamID> = env.team_ids();
+    teams.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // Keyed by (team, player); the team's own totals use an empty player name.
+    let mut simulated: BTreeMap<(String, String), SeasonLine> = BTreeMap::new();
+    for (n, (home, away)) in schedule(&teams, games_per_team)?.iter().enumerate() {
+        let summary = simulate_game(
+            env,
+            home,
+            away,
+            seed.wrapping_add(n as u64),
+            options.clone(),
+        )?;
+
+        for (team, points) in [(home, summary.home_score), (away, summary.away_score)] {
+            let line = simulated
+                .entry((team.name.clone(), String::new()))
+                .or_insert_with(|| SeasonLine::simulated(&team.name, ""));
+            line.games += 1;
+            line.points = line.points.map(|p| p + points);
+        }
+        for stats in summary.players.values() {
+            let team_line = simulated
+                .get_mut(&(stats.team.clone(), String::new()))
+                .ok_or(format!("{} is not in this game", stats.team))?;
+            team_line.add_stats(stats);
+            let games = team_line.games;
+
+            let line = simulated
+                .entry((stats.team.clone(), stats.name.clone()))
+                .or_insert_with(|| SeasonLine::simulated(&stats.team, &stats.name));
+            line.add_stats(stats);
+            line.games = games;
+        }
+    }
+
+    let mut rows = vec![];
+    let mut unmatched = vec![];
+    for actual in reference {
+        let key = (actual.team.clone(), actual.player.clone());
+        let sim = match simulated.get(&key) {
+            Some(s) => s,
+            None => {
+                unmatched.push(
+                    format!("{} {}", actual.team, actual.player)
+                        .trim()
+                        .to_string(),
+                );
+                continue;
+            }
+        };
+        rows.extend(compare(sim, actual));
+    }
+
+    Ok(CalibrationReport {
+        games_per_team,
+        seed,
+        rows,
+        unmatched,
+    })
+}
+
+/// Every metric both lines can compute, simulated against actual.
+fn compare(simulated: &SeasonLine, actual: &SeasonLine) -> Vec<CalibrationRow> {
+    simulated
+        .metrics()
+        .into_iter()
+        .zip(actual.metrics())
+        .filter_map(|((metric, sim), (_, act))| {
+            let (sim, act) = (sim?, act?);
+            let diff = sim - act;
+            Some(CalibrationRow {
+                team: actual.team.clone(),
+                player: actual.player.clone(),
+                metric: metric.to_string(),
+                simulated: sim,
+                actual: act,
+                diff,
+                pct_diff: (act != 0.0).then(|| diff / act * 100.0),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reference_allows_missing_columns() {
+        let csv = "team,player,games,points,rush_att,rush_yds,pass_att,pass_cmp,pass_yds,pass_int,sacked,rec,rec_yds\n\
+                   Chicago,,16,320,,,,,,,,,\n\
+                   Chicago,Walter Payton,16,,300,1200,,,,,,50,400\n";
+        let lines = parse_reference(csv.as_bytes()).expect("reference parses");
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].player, "");
+        assert_eq!(lines[0].points, Some(320));
+        assert_eq!(lines[0].rush_att, None);
+        assert_eq!(lines[1].player, "Walter Payton");
+        assert_eq!(lines[1].rush_yds, Some(1200));
+    }
+
+    #[test]
+    fn test_compare_normalizes_and_skips_missing_metrics() {
+        let sim = SeasonLine {
+            games: 2,
+            rush_att: Some(60),
+            rush_yds: Some(240),
+            ..SeasonLine::simulated("Chicago", "")
+        };
+        let actual = SeasonLine {
+            team: "Chicago".into(),
+            games: 16,
+            rush_att: Some(480),
+            rush_yds: Some(2400),
+            ..SeasonLine::default()
+        };
+
+        let rows = compare(&sim, &actual);
+        let metric = |m: &str| rows.iter().find(|r| r.metric == m);
+
+        // (metric, simulated, actual)
+        let cases = [
+            ("rush_att_per_game", 30.0, 30.0),
+            ("rush_yds_per_game", 120.0, 150.0),
+            ("yds_per_carry", 4.0, 5.0),
+        ];
+        for (name, sim, act) in cases {
+            let row = metric(name).unwrap_or_else(|| panic!("{} missing", name));
+            assert_eq!((row.simulated, row.actual), (sim, act), "{}", name);
+        }
+        assert_eq!(metric("yds_per_carry").unwrap().pct_diff, Some(-20.0));
+        assert!(
+            metric("cmp_pct").is_none(),
+            "no passing in the reference, so no passing metrics"
+        );
+    }
+
+    fn league(size: usize) -> Vec<TeamID> {
+        (0..size)
+            .map(|n| TeamID {
+                name: format!("T{}", n),
+                year: "1983".into(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_schedule_gives_every_team_exactly_its_games() {
+        // (teams, games per team)
+        for (size, per_team) in [(2, 1), (3, 4), (4, 3), (4, 16), (28, 16), (28, 3)] {
+            let teams = league(size);
+            let games = schedule(&teams, per_team).unwrap();
+
+            assert_eq!(games.len(), size * per_team as usize / 2);
+            for t in &teams {
+                let home = games.iter().filter(|(h, _)| h == t).count();
+                let away = games.iter().filter(|(_, a)| a == t).count();
+                assert_eq!(home + away, per_team as usize, "{} in {:?}", t.name, games);
+                assert!(
+                    home.abs_diff(away) <= 1,
+                    "{} is home {} times",
+                    t.name,
+                    home
+                );
+            }
+            assert!(games.iter().all(|(h, a)| h != a), "no team plays itself");
+        }
+    }
+
+    #[test]
+    fn test_schedule_needs_an_even_league_for_odd_game_counts() {
+        assert!(schedule(&league(3), 3).is_err());
+        assert!(schedule(&league(3), 2).is_ok());
+    }
+}
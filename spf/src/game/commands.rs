@@ -0,0 +1,204 @@
+//! The command side of the WebSocket protocol: what a client may ask the game to do over the
+//! socket, and the acknowledgement it gets back. Commands go through the same `Game` methods
+//! as the REST endpoints, after the same seat check ([`Game::authorize`]); their effects
+//! reach every client as the usual [`GameEvent`](super::events::GameEvent)s.
+//!
+//! A command frame is `{"id": "<correlation id>", "command": "<Variant>", "data": { … }}`
+//! (`data` omitted for commands without arguments). Every command frame is answered with
+//! `{"ack": "<id>", "ok": true}` or `{"ack": "<id>", "ok": false, "code": "…", "error": "…"}`,
+//! with the same `code`s as the REST API's error bodies.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{
+    engine::{DefenseCall, DefenseIDLineup, OffenseCall, OffenseIDLineup, PlayType},
+    error::GameError,
+    lineup::{FormationLineup, LineupChanges},
+    seats::{Seat, SeatAction},
+    Game,
+};
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(tag = "command", content = "data")]
+pub enum GameCommand {
+    SetOffensiveLineup { lineup: OffenseIDLineup },
+    SetDefensiveLineup { lineup: DefenseIDLineup },
+    SetOffensiveFormation { formation: FormationLineup },
+    SetDefensiveFormation { formation: FormationLineup },
+    ChangeOffensiveLineup { changes: LineupChanges },
+    ChangeDefensiveLineup { changes: LineupChanges },
+    SetOffenseCall { call: OffenseCall },
+    SetDefenseCall { call: DefenseCall },
+    SetNextPlayType { play_type: PlayType },
+    RunPlay,
+    Undo { to: Option<u32> },
+}
+
+impl GameCommand {
+    fn action(&self) -> SeatAction {
+        match self {
+            GameCommand::SetOffensiveLineup { .. }
+            | GameCommand::SetOffensiveFormation { .. }
+            | GameCommand::ChangeOffensiveLineup { .. }
+            | GameCommand::SetOffenseCall { .. }
+            | GameCommand::SetNextPlayType { .. } => SeatAction::Offense,
+            GameCommand::SetDefensiveLineup { .. }
+            | GameCommand::SetDefensiveFormation { .. }
+            | GameCommand::ChangeDefensiveLineup { .. }
+            | GameCommand::SetDefenseCall { .. } => SeatAction::Defense,
+            GameCommand::RunPlay | GameCommand::Undo { .. } => SeatAction::Coach,
+        }
+    }
+}
+
+/// A command as it arrives on the socket, with the client's correlation id.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommandFrame {
+    pub id: Option<String>,
+    #[serde(flatten)]
+    pub command: GameCommand,
+}
+
+/// The reply to one command frame.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CommandAck {
+    /// The `id` of the command this answers.
+    pub ack: Option<String>,
+    pub ok: bool,
+    /// Machine-readable kind of failure, e.g. `not_allowed` or `malformed_command`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CommandAck {
+    fn ok(ack: Option<String>) -> Self {
+        Self {
+            ack,
+            ok: true,
+            code: None,
+            error: None,
+        }
+    }
+
+    fn failed(ack: Option<String>, code: &str, error: String) -> Self {
+        Self {
+            ack,
+            ok: false,
+            code: Some(code.to_string()),
+            error: Some(error),
+        }
+    }
+
+    fn new(ack: Option<String>, result: Result<(), GameError>) -> Self {
+        match result {
+            Ok(()) => Self::ok(ack),
+            Err(e) => Self::failed(ack, e.code(), e.to_string()),
+        }
+    }
+}
+
+impl Game {
+    /// Run `command` on behalf of `seat`, if that seat may.
+    pub fn execute(&mut self, seat: Seat, command: GameCommand) -> Result<(), GameError> {
+        self.authorize(seat, command.action())?;
+        match command {
+            GameCommand::SetOffensiveLineup { lineup } => {
+                self.set_offensive_lineup_from_ids(&lineup)
+            }
+            GameCommand::SetDefensiveLineup { lineup } => {
+                self.set_defensive_lineup_from_ids(&lineup)
+            }
+            GameCommand::SetOffensiveFormation { formation } => {
+                self.set_offensive_lineup_from_formation(&formation)
+            }
+            GameCommand::SetDefensiveFormation { formation } => {
+                self.set_defensive_lineup_from_formation(&formation)
+            }
+            GameCommand::ChangeOffensiveLineup { changes } => {
+                self.change_offensive_lineup(&changes)
+            }
+            GameCommand::ChangeDefensiveLineup { changes } => {
+                self.change_defensive_lineup(&changes)
+            }
+            GameCommand::SetOffenseCall { call } => self.set_offense_call(call),
+            GameCommand::SetDefenseCall { call } => self.set_defense_call(call),
+            GameCommand::SetNextPlayType { play_type } => self.set_next_play_type(play_type),
+            GameCommand::RunPlay => self.run_current_play().map(|_| ()),
+            GameCommand::Undo { to } => self.undo(to).map(|_| ()),
+        }
+    }
+
+    /// Parse a raw command frame, run it for `seat` and build the acknowledgement. A frame
+    /// that doesn't parse is still answered (with whatever `id` could be read from it).
+    pub fn handle_command_frame(&mut self, seat: Seat, text: &str) -> CommandAck {
+        match serde_json::from_str::<CommandFrame>(text) {
+            Ok(frame) => CommandAck::new(frame.id, self.execute(seat, frame.command)),
+            Err(e) => {
+                let id = serde_json::from_str::<serde_json::Value>(text)
+                    .ok()
+                    .and_then(|v| v.get("id").and_then(|id| id.as_str()).map(String::from));
+                CommandAck::failed(id, "malformed_command", format!("Malformed command: {}", e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{fac::FacManager, players::TeamID, GameOptions};
+    use spf_core::players::{Player, Roster};
+
+    fn game() -> Game {
+        let roster = |name: &str| {
+            Roster::from_players(
+                TeamID {
+                    name: name.into(),
+                    year: "1983".into(),
+                },
+                Vec::<Player>::new(),
+            )
+        };
+        Game::build(
+            roster("Home"),
+            roster("Away"),
+            FacManager::from_cards(vec![]),
+            GameOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_command_is_acked_with_its_id() {
+        let mut game = game();
+        let ack = game.handle_command_frame(
+            Seat::Away,
+            r#"{"id":"c1","command":"SetNextPlayType","data":{"play_type":"Kickoff"}}"#,
+        );
+        assert_eq!(ack.ack.as_deref(), Some("c1"));
+        assert!(ack.ok, "{:?}", ack.error);
+    }
+
+    #[test]
+    fn test_command_from_the_wrong_seat_is_refused() {
+        let mut game = game();
+        let ack = game.handle_command_frame(
+            Seat::Home,
+            r#"{"id":"c2","command":"SetOffenseCall","data":{"call":{"kind":"Onside"}}}"#,
+        );
+        assert!(!ack.ok);
+        assert_eq!(ack.code.as_deref(), Some("not_allowed"));
+        assert!(!game.get_offense_call(Seat::Away).set);
+    }
+
+    #[test]
+    fn test_malformed_command_still_gets_an_ack() {
+        let mut game = game();
+        let ack = game.handle_command_frame(Seat::Away, r#"{"id":"c3","command":"Fly"}"#);
+        assert_eq!(ack.ack.as_deref(), Some("c3"));
+        assert!(!ack.ok);
+        assert!(ack.error.unwrap().starts_with("Malformed command"));
+    }
+}
@@ -0,0 +1,120 @@
+//! Depth charts in play: a coach saves named lineups for their team (kept on the team's
+//! [`Roster`](super::players::Roster) and written back to its data file by the
+//! environment), then sets a play's lineup by formation name plus that play's
+//! substitutions, or changes a few players of the lineup already set (or carried over from
+//! the last play). The resolved lineup goes through the usual setters, so it is validated,
+//! logged and broadcast exactly like one posted in full.
+
+use super::{
+    engine::{DefenseIDLineup, OffenseIDLineup},
+    error::GameError,
+    lineup::{
+        DepthChart, FormationLineup, LineupChanges, StandardIDDefenseLineup,
+        StandardIDOffenseLineup,
+    },
+    players::Roster,
+    Game, GameTeams,
+};
+
+impl Game {
+    pub fn team_roster(&self, team: GameTeams) -> &Roster {
+        match team {
+            GameTeams::Home => &self.home,
+            GameTeams::Away => &self.away,
+        }
+    }
+
+    fn team_roster_mut(&mut self, team: GameTeams) -> &mut Roster {
+        match team {
+            GameTeams::Home => &mut self.home,
+            GameTeams::Away => &mut self.away,
+        }
+    }
+
+    pub fn depth_chart(&self, team: GameTeams) -> &DepthChart {
+        self.team_roster(team).depth_chart()
+    }
+
+    /// Save (or replace) one of `team`'s offensive formations. Only legal lineups of the
+    /// team's own players are kept.
+    pub fn save_offense_formation(
+        &mut self,
+        team: GameTeams,
+        name: String,
+        lineup: StandardIDOffenseLineup,
+    ) -> Result<&DepthChart, GameError> {
+        if let Some(problem) = lineup.problems(self.team_roster(team)).into_iter().next() {
+            return Err(problem.into());
+        }
+        let roster = self.team_roster_mut(team);
+        roster.depth_chart_mut().offense.insert(name, lineup);
+        Ok(roster.depth_chart())
+    }
+
+    /// Save (or replace) one of `team`'s defensive formations.
+    pub fn save_defense_formation(
+        &mut self,
+        team: GameTeams,
+        name: String,
+        lineup: StandardIDDefenseLineup,
+    ) -> Result<&DepthChart, GameError> {
+        if let Some(problem) = lineup.problems(self.team_roster(team)).into_iter().next() {
+            return Err(problem.into());
+        }
+        let roster = self.team_roster_mut(team);
+        roster.depth_chart_mut().defense.insert(name, lineup);
+        Ok(roster.depth_chart())
+    }
+
+    /// Set the offense's lineup from one of its formations.
+    pub fn set_offensive_lineup_from_formation(
+        &mut self,
+        pick: &FormationLineup,
+    ) -> Result<(), GameError> {
+        let lineup = self
+            .get_current_off_roster()
+            .depth_chart()
+            .offense_lineup(pick)?;
+        self.set_offensive_lineup_from_ids(&OffenseIDLineup::StandardIDOffenseLineup(lineup))
+    }
+
+    /// Set the defense's lineup from one of its formations.
+    pub fn set_defensive_lineup_from_formation(
+        &mut self,
+        pick: &FormationLineup,
+    ) -> Result<(), GameError> {
+        let lineup = self
+            .get_current_def_roster()
+            .depth_chart()
+            .defense_lineup(pick)?;
+        self.set_defensive_lineup_from_ids(&DefenseIDLineup::StandardIDDefenseLineup(lineup))
+    }
+
+    /// Make `changes` to the offense's lineup for the pending play.
+    pub fn change_offensive_lineup(&mut self, changes: &LineupChanges) -> Result<(), GameError> {
+        let lineup = match &self.offlineup {
+            Some(OffenseIDLineup::StandardIDOffenseLineup(l)) => {
+                OffenseIDLineup::StandardIDOffenseLineup(changes.apply(l)?)
+            }
+            Some(OffenseIDLineup::KickoffIDOffenseLineup(l)) => {
+                OffenseIDLineup::KickoffIDOffenseLineup(changes.apply(l)?)
+            }
+            None => return Err(GameError::NotReady("No offensive lineup to change".into())),
+        };
+        self.set_offensive_lineup_from_ids(&lineup)
+    }
+
+    /// Make `changes` to the defense's lineup for the pending play.
+    pub fn change_defensive_lineup(&mut self, changes: &LineupChanges) -> Result<(), GameError> {
+        let lineup = match &self.deflineup {
+            Some(DefenseIDLineup::StandardIDDefenseLineup(l)) => {
+                DefenseIDLineup::StandardIDDefenseLineup(changes.apply(l)?)
+            }
+            Some(DefenseIDLineup::KickoffIDDefenseLineup(l)) => {
+                DefenseIDLineup::KickoffIDDefenseLineup(changes.apply(l)?)
+            }
+            None => return Err(GameError::NotReady("No defensive lineup to change".into())),
+        };
+        self.set_defensive_lineup_from_ids(&lineup)
+    }
+}
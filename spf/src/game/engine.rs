@@ -1,3 +1,4 @@
+pub mod clockplay;
 pub mod defs;
 pub mod kickplay;
 pub mod passplay;
@@ -9,6 +10,7 @@ use enum_as_inner::EnumAsInner;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use spf_core::trace;
 use strum_macros::EnumString;
 
 use crate::game::lineup::KickoffIDDefenseLineup;
@@ -16,6 +18,7 @@ use crate::game::lineup::KickoffIDDefenseLineup;
 use self::resulthandler::calculate_play_result;
 
 use super::{
+    error::GameError,
     fac::{FacCard, FacData, FacManager},
     kickoff_play::KickoffPlay,
     lineup::{KickoffIDOffenseLineup, StandardIDDefenseLineup, StandardIDOffenseLineup},
@@ -67,19 +70,19 @@ impl Down {
 }
 
 pub trait PlayImpl: Send {
-    fn validate(&self) -> Result<(), String>;
-    fn set_offense_call(&mut self, call: OffenseCall) -> Result<(), String>;
-    fn set_defense_call(&mut self, call: DefenseCall) -> Result<(), String>;
+    fn validate(&self, game_state: &GameState) -> Result<(), GameError>;
+    fn set_offense_call(&mut self, call: OffenseCall) -> Result<(), GameError>;
+    fn set_defense_call(&mut self, call: DefenseCall) -> Result<(), GameError>;
     fn set_offense_lineup(
         &mut self,
         lineup: &OffenseIDLineup,
         roster: &Roster,
-    ) -> Result<(), String>;
+    ) -> Result<(), GameError>;
     fn set_defense_lineup(
         &mut self,
         lineup: &DefenseIDLineup,
         roster: &Roster,
-    ) -> Result<(), String>;
+    ) -> Result<(), GameError>;
     fn run_play<'a>(
         &'a self,
         game_state: &'a GameState,
@@ -100,7 +103,7 @@ pub struct IDOffensivePlay {
     pub target_id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, EnumString, PartialEq, Copy, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, EnumString, PartialEq, Eq, Hash, Copy, ToSchema)]
 pub enum PlayType {
     Kickoff,
     Punt,
@@ -152,7 +155,7 @@ impl<'a> CardStreamer<'a> {
             match card {
                 FacCard::Z => {
                     if self.cards_flipped.len() <= 3 {
-                        println!("Z Event");
+                        trace!("Z Event");
                         self.had_z = true;
                     }
                 }
@@ -181,6 +184,31 @@ pub enum ResultType {
     TurnOver,
 }
 
+/// Who carried, threw or caught the ball on a play, keyed by player id. Recorded by the play
+/// engine so box scores and simulations don't have to parse the play-by-play text.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub enum PlayStat {
+    Rush {
+        player: String,
+        yards: Yard,
+    },
+    Pass {
+        passer: String,
+        receiver: Option<String>,
+        complete: bool,
+        yards: Yard,
+        touchdown: bool,
+    },
+    Sack {
+        passer: String,
+        yards: Yard,
+    },
+    Interception {
+        passer: String,
+        defender: String,
+    },
+}
+
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct PlayResult {
     pub result_type: ResultType,
@@ -192,18 +220,21 @@ pub struct PlayResult {
 
     pub extra: Option<String>,
     pub cards: CardResults,
+    pub stat: Option<PlayStat>,
+    /// The play used the offense's big-play chance for the drive.
+    pub big_play_tried: bool,
 }
 
 pub fn run_play(
     game_state: &GameState,
     fac_deck: &mut FacManager,
     play: &Box<dyn PlayImpl + Send>,
-) -> Result<PlayAndState, String> {
-    play.validate()?;
+) -> Result<PlayAndState, GameError> {
+    play.validate(game_state)?;
 
     let mut card_streamer = CardStreamer::new(fac_deck);
 
-    println!("Will Run Play");
+    trace!("Will Run Play");
     let result = play.run_play(game_state, &mut card_streamer);
 
     if result.cards.had_z {
@@ -243,7 +274,8 @@ impl_deserialize!(DefenseIDLineup {
     StandardIDDefenseLineup(StandardIDDefenseLineup)
 });
 
-#[derive(Debug, Clone, EnumAsInner)]
+#[derive(Debug, Clone, EnumAsInner, Serialize)]
+#[serde(untagged)]
 pub enum DefenseCall {
     StandardDefenseCall(StandardDefenseCall),
     KickoffDefenseCall(KickoffDefenseCall),
@@ -258,7 +290,8 @@ impl_deserialize!(DefenseCall {
     PuntDefenseCall(PuntDefenseCall)
 });
 
-#[derive(Debug, Clone, EnumAsInner)]
+#[derive(Debug, Clone, EnumAsInner, Serialize)]
+#[serde(untagged)]
 pub enum OffenseCall {
     StandardOffenseCall(StandardOffenseCall),
     KickoffOffenseCall(KickoffOffenseCall),
@@ -267,10 +300,11 @@ pub enum OffenseCall {
     PuntOffenseCall(PuntOffenseCall),
 }
 
+// Every kickoff field is optional, so the kickoff call has to be tried last.
 impl_deserialize!(OffenseCall {
     StandardOffenseCall(StandardOffenseCall),
-    KickoffOffenseCall(KickoffOffenseCall),
-    PuntOffenseCall(PuntOffenseCall)
+    PuntOffenseCall(PuntOffenseCall),
+    KickoffOffenseCall(KickoffOffenseCall)
 });
 
 impl utoipa::PartialSchema for OffenseCall {
@@ -332,24 +366,96 @@ impl utoipa::ToSchema for DefenseCall {
 //     }
 // }
 
-#[derive(Debug, Clone, Deserialize, ToSchema)]
-pub struct KickoffDefenseCall {}
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KickoffDefenseCall {
+    /// The hands team is on the field, so an onside kick is much harder to recover.
+    #[serde(default)]
+    pub onside_expected: bool,
+}
+
+/// How the kicking team kicks off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum KickoffKind {
+    #[default]
+    Normal,
+    /// A low, bouncing kick: never a touchback and only a short return, but the receivers
+    /// start further upfield.
+    Squib,
+    /// A short kick the kicking team tries to recover.
+    Onside,
+}
 
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(from = "KickoffOffenseCallFields")]
 pub struct KickoffOffenseCall {
-    pub onside: bool,
+    #[serde(default)]
+    pub kind: KickoffKind,
+}
+
+/// A kickoff call as sent: a `kind`, or the `onside` flag calls used before there were
+/// kinds. Neither means a normal kick.
+#[derive(Deserialize)]
+struct KickoffOffenseCallFields {
+    #[serde(default)]
+    kind: Option<KickoffKind>,
+    #[serde(default)]
+    onside: bool,
 }
 
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+impl From<KickoffOffenseCallFields> for KickoffOffenseCall {
+    fn from(fields: KickoffOffenseCallFields) -> Self {
+        let kind = match fields.kind {
+            Some(kind) => kind,
+            None if fields.onside => KickoffKind::Onside,
+            None => KickoffKind::Normal,
+        };
+        KickoffOffenseCall { kind }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PuntDefenseCall {
     // TODO(punts): read once punt handling is implemented (Backlog.md -> Special Teams).
     #[allow(dead_code)]
     pub attempt_block: bool,
 }
 
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PuntOffenseCall {
     // TODO(punts): read once punt handling is implemented (Backlog.md -> Special Teams).
     #[allow(dead_code)]
     pub coffin_corner: i32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offense_call(json: serde_json::Value) -> OffenseCall {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_kickoff_call_reads_the_old_onside_flag() {
+        let kind = |json| offense_call(json).into_kickoff_offense_call().unwrap().kind;
+        assert_eq!(
+            kind(serde_json::json!({"onside": true})),
+            KickoffKind::Onside
+        );
+        assert_eq!(
+            kind(serde_json::json!({"onside": false})),
+            KickoffKind::Normal
+        );
+        assert_eq!(kind(serde_json::json!({})), KickoffKind::Normal);
+        assert_eq!(
+            kind(serde_json::json!({"kind": "Squib", "onside": true})),
+            KickoffKind::Squib
+        );
+    }
+
+    #[test]
+    fn test_punt_call_is_not_taken_for_a_kickoff() {
+        let call = offense_call(serde_json::json!({"coffin_corner": 5}));
+        assert!(call.as_punt_offense_call().is_some());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    detail,
+    game::{
+        engine::defs::{FLOP_YARDS, TIMES},
+        lineup::OffensiveBox,
+        standard_play::PlaySetup,
+        GameState,
+    },
+};
+
+use super::{playutils::PlayUtils, CardStreamer, PlayResult, PlayStat, ResultType};
+
+/// Plays that only manage the clock: no FAC is flipped and nothing can go wrong.
+pub struct ClockUtils {}
+impl ClockUtils {
+    /// Flop: the quarterback takes a knee for a small loss and the clock keeps running.
+    pub fn handle_flop<'a>(
+        state: &'a GameState,
+        play: PlaySetup<'a>,
+        cards: &'a mut CardStreamer<'a>,
+    ) -> PlayResult {
+        let mut utils = PlayUtils::new(state, cards);
+        let qb = ClockUtils::qb_id(&play);
+        detail!(utils, "The quarterback takes a knee");
+        utils.record_stat(PlayStat::Rush {
+            player: qb,
+            yards: FLOP_YARDS,
+        });
+        ClockUtils::create_result(state, &utils, FLOP_YARDS, TIMES.flop)
+    }
+
+    /// Spike: the quarterback throws the ball into the ground, an incomplete pass that
+    /// stops the clock at once.
+    pub fn handle_spike<'a>(
+        state: &'a GameState,
+        play: PlaySetup<'a>,
+        cards: &'a mut CardStreamer<'a>,
+    ) -> PlayResult {
+        let mut utils = PlayUtils::new(state, cards);
+        let qb = ClockUtils::qb_id(&play);
+        detail!(utils, "The quarterback spikes the ball");
+        utils.record_stat(PlayStat::Pass {
+            passer: qb,
+            receiver: None,
+            complete: false,
+            yards: 0,
+            touchdown: false,
+        });
+        ClockUtils::create_result(state, &utils, 0, TIMES.spike)
+    }
+
+    fn qb_id(play: &PlaySetup) -> String {
+        play.offense
+            .get_player_in_pos(&OffensiveBox::QB)
+            .map(|qb| qb.get_id())
+            .unwrap_or_default()
+    }
+
+    fn create_result(state: &GameState, utils: &PlayUtils, result: i32, time: i32) -> PlayResult {
+        PlayResult {
+            result_type: ResultType::Regular,
+            result,
+            final_line: result + state.yard_line,
+            time,
+
+            ..utils.result()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        standard_play::{DefensivePlay, OffensivePlayType, OffensiveStrategy, StandardOffenseCall},
+        testutils::{card, defense_call, offense_call, roster, run, standard_play, state},
+    };
+
+    fn clock_play(strategy: OffensiveStrategy) -> PlayResult {
+        let play = standard_play(
+            &roster("Home"),
+            &roster("Away"),
+            StandardOffenseCall {
+                strategy,
+                ..offense_call(OffensivePlayType::SH, OffensiveBox::QB)
+            },
+            defense_call(DefensivePlay::PassDefense),
+        );
+        run(&state(), vec![card(1)], play).unwrap().result
+    }
+
+    #[test]
+    fn test_flop_is_a_short_loss_on_a_running_clock() {
+        let flop = clock_play(OffensiveStrategy::Flop);
+        assert_eq!((flop.result, flop.time), (FLOP_YARDS, TIMES.flop));
+        assert!(matches!(
+            &flop.stat,
+            Some(PlayStat::Rush { player, yards }) if player == "QB-1" && *yards == FLOP_YARDS
+        ));
+    }
+
+    #[test]
+    fn test_spike_is_an_incomplete_pass_to_nobody() {
+        let spike = clock_play(OffensiveStrategy::Spike);
+        assert_eq!((spike.result, spike.time), (0, TIMES.spike));
+        assert!(matches!(
+            spike.stat,
+            Some(PlayStat::Pass {
+                complete: false,
+                receiver: None,
+                ..
+            })
+        ));
+    }
+}
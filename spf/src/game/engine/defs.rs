@@ -19,6 +19,8 @@ pub struct TimeTable {
     pub run_play_ob: i32,
     pub pass_play_complete: i32,
     pub pass_play_incomplete: i32,
+    pub flop: i32,
+    pub spike: i32,
 }
 
 pub struct GameConstants {
@@ -27,7 +29,36 @@ pub struct GameConstants {
     pub points_for_td: i32,
     pub points_for_safety: i32,
     pub touchback_line: Yard,
-    pub onside_kick_line: Yard,
+}
+
+/// The team card's big-play rule: once a drive, the offense's first gain gets a chance,
+/// from its home or road rating, to break open.
+pub struct BigPlayRules {
+    /// A big play comes on a pass number up to the rating times this...
+    pub chance_per_point: i32,
+    /// ...and multiplies the gain by this.
+    pub multiplier: i32,
+}
+
+/// Where kickoffs are spotted and how the kick variants play out. The kickoff results
+/// tables assume a kick from `kick_line`; kicks from elsewhere shift their lines.
+pub struct KickoffRules {
+    pub kick_line: Yard,
+    /// The free kick after a safety.
+    pub safety_kick_line: Yard,
+    /// A kick out of bounds gives the receivers the ball this far past the kicking line.
+    pub out_of_bounds_distance: Yard,
+    /// An onside kick is recovered this far past the kicking line...
+    pub onside_distance: Yard,
+    /// ...by the kicking team on a pass number up to this, or this against the hands team.
+    pub onside_recover: i32,
+    pub onside_recover_expected: i32,
+    /// Where a squib from `kick_line` is fielded, from the receiving team's goal line.
+    pub squib_line: Yard,
+    /// The most tacklers a kicking team can name to cover a kick.
+    pub max_coverage: usize,
+    /// A returner's fumble is recovered by the kicking team on a pass number up to this.
+    pub fumble_recover: i32,
 }
 
 #[derive(Debug, Clone)]
@@ -64,6 +95,22 @@ pub struct DefenseConsts {
     pub triple_cover_defense: i32,
 }
 
+/// Limits of a quarterback sneak: only called this close to the sticks, and the push
+/// never goes further than these either way.
+pub struct QbSneakRules {
+    pub max_to_go: Yard,
+    pub max_gain: Yard,
+    pub max_loss: Yard,
+}
+
+/// Passing inside the opponent's 20: there's no room to throw deep, and the defense has
+/// less field to cover on everything else.
+pub struct RedZoneRules {
+    pub start: Yard,
+    pub qk_defense: i32,
+    pub sh_defense: i32,
+}
+
 #[derive(Clone)]
 pub struct DefenseStrategyRowVals {
     pub row2: i32,
@@ -90,14 +137,37 @@ pub struct PassPlayValues {
     pub pa_run_defense: i32,
     pub pa_pass_defense: i32,
     pub pa_prevent_defense: i32,
+
+    pub exceptional_receiver: i32,
 }
 
+/// A flop (quarterback kneel) always loses this much.
+pub const FLOP_YARDS: Yard = -1;
+
+/// A long gain off the card goes four yards a run number, never more than this. A receiver
+/// whose long-gain letter beats it is an exceptional receiver.
+pub const LONG_GAIN_YARDS: Yard = 30;
+
+/// Intentional grounding is marked off this far from the line of scrimmage, or half the
+/// distance to the goal when that is closer.
+pub const GROUNDING_YARDS: Yard = 10;
+
+/// Who can carry on a run: a back, or the quarterback on a designed run or sneak.
+const RUN_TARGETS: [OffensiveBox; 4] = [
+    OffensiveBox::B1,
+    OffensiveBox::B2,
+    OffensiveBox::B3,
+    OffensiveBox::QB,
+];
+
 lazy_static! {
     pub static ref TIMES: TimeTable = TimeTable {
         run_play: 40,
         run_play_ob: 10,
         pass_play_complete: 40,
         pass_play_incomplete: 10,
+        flop: 40,
+        spike: 3,
     };
 
     pub static ref GAMECONSTANTS: GameConstants = GameConstants {
@@ -106,9 +176,37 @@ lazy_static! {
         points_for_td: 6,
         points_for_safety: 2,
         touchback_line: 20,
-        onside_kick_line: 50,
     };
 
+    pub static ref BIG_PLAY: BigPlayRules = BigPlayRules {
+        chance_per_point: 3,
+        multiplier: 2,
+    };
+
+    pub static ref KICKOFF: KickoffRules = KickoffRules {
+        kick_line: 35,
+        safety_kick_line: 20,
+        out_of_bounds_distance: 30,
+        onside_distance: 10,
+        onside_recover: 11,
+        onside_recover_expected: 4,
+        squib_line: 25,
+        max_coverage: 4,
+        fumble_recover: 24,
+    };
+
+
+    pub static ref QB_SNEAK: QbSneakRules = QbSneakRules {
+        max_to_go: 2,
+        max_gain: 3,
+        max_loss: -1,
+    };
+
+    pub static ref RED_ZONE: RedZoneRules = RedZoneRules {
+        start: 20,
+        qk_defense: -3,
+        sh_defense: -5,
+    };
 
     pub static ref DRAW_IMPACT: DrawPlayImpact = DrawPlayImpact {
         run_defense: 2,
@@ -149,6 +247,7 @@ lazy_static! {
         pa_run_defense: 5,
         pa_pass_defense: -5,
         pa_prevent_defense: -10,
+        exceptional_receiver: 3,
     };
 
     pub static ref PASS_DEFENDERS: HashMap<OffensiveBox, DefensiveBox> = {
@@ -271,7 +370,7 @@ lazy_static! {
                 }),
                 name: "Sweep Left",
                 code: "SL",
-                allowed_targets: vec![OffensiveBox::B1, OffensiveBox::B2, OffensiveBox::B3],
+                allowed_targets: RUN_TARGETS.to_vec(),
                 handler: RunUtils::handle_run_play,
             },
         );
@@ -285,7 +384,7 @@ lazy_static! {
                 }),
                 name: "Sweep Right",
                 code: "SR",
-                allowed_targets: vec![OffensiveBox::B1, OffensiveBox::B2, OffensiveBox::B3],
+                allowed_targets: RUN_TARGETS.to_vec(),
                 handler: RunUtils::handle_run_play,
             },
         );
@@ -299,7 +398,7 @@ lazy_static! {
                 }),
                 name: "Inside Left",
                 code: "IL",
-                allowed_targets: vec![OffensiveBox::B1, OffensiveBox::B2, OffensiveBox::B3],
+                allowed_targets: RUN_TARGETS.to_vec(),
                 handler: RunUtils::handle_run_play,
             },
         );
@@ -313,7 +412,7 @@ lazy_static! {
                 }),
                 name: "Inside Right",
                 code: "IR",
-                allowed_targets: vec![OffensiveBox::B1, OffensiveBox::B2, OffensiveBox::B3],
+                allowed_targets: RUN_TARGETS.to_vec(),
                 handler: RunUtils::handle_run_play,
             },
         );
@@ -1,9 +1,13 @@
+use std::cmp::Ordering;
+
+use spf_core::trace;
+
 use crate::{
     detail,
     game::{
-        engine::defs::{GAMECONSTANTS, KICKOFFRESULTSB},
-        players::Returner,
-        GameState,
+        engine::defs::{GAMECONSTANTS, KICKOFF, KICKOFFRESULTSB},
+        players::{PlayerUtils, Returner},
+        GamePlayStatus, GameState,
     },
     mechanic,
 };
@@ -11,12 +15,13 @@ use crate::{
 use super::{
     defs::{KickoffResult, KICKOFFRESULTSA},
     playutils::PlayUtils,
-    CardStreamer, KickoffPlay, PlayResult, ResultType, Yard,
+    CardStreamer, KickoffKind, KickoffPlay, PlayResult, ResultType, Yard,
 };
 
 pub struct KickPlayImpl<'a> {
     utils: PlayUtils<'a>,
     play: &'a KickoffPlay,
+    state: &'a GameState,
 }
 
 impl<'a> KickPlayImpl<'a> {
@@ -28,41 +33,96 @@ impl<'a> KickPlayImpl<'a> {
         let mut kpc = KickPlayImpl {
             utils: PlayUtils::new(state, cards),
             play,
+            state,
         };
 
         kpc.run_kickoff()
     }
 
     fn run_kickoff(&mut self) -> PlayResult {
-        println!("Running Kickoff");
-        if self.play.onside.unwrap_or(false) {
-            self.run_onside_kick()
-        } else {
-            let num = self.utils.get_run_num();
-            self.run_result(KICKOFFRESULTSA.get(&num).unwrap())
+        trace!("Running Kickoff");
+        let kick_line = self.get_kick_line();
+        match self.play.kind.unwrap_or_default() {
+            KickoffKind::Onside => self.run_onside_kick(kick_line),
+            KickoffKind::Squib => self.run_squib_kick(kick_line),
+            KickoffKind::Normal => {
+                let num = self.utils.get_run_num();
+                self.run_result(KICKOFFRESULTSA.get(&num).unwrap(), kick_line)
+            }
         }
     }
 
-    fn run_onside_kick(&mut self) -> PlayResult {
+    /// The kicking team's own yard line: the free kick after a safety comes from deeper.
+    fn get_kick_line(&mut self) -> Yard {
+        let line = if self.state.last_status == GamePlayStatus::Safety {
+            detail!(self.utils, "A free kick after the safety");
+            KICKOFF.safety_kick_line
+        } else {
+            KICKOFF.kick_line
+        };
+        mechanic!(self.utils, "Kicking from the {}", line);
+        line
+    }
+
+    fn run_onside_kick(&mut self, kick_line: Yard) -> PlayResult {
         detail!(self.utils, "An onside kick is tried");
-        let result_type = match self.utils.get_pass_num() {
-            1..=11 => {
-                detail!(self.utils, "Recovered by the kicking team");
-                ResultType::Regular
-            }
-            _ => {
-                detail!(self.utils, "Recovered by the receiving team");
-                ResultType::TurnOver
-            }
+        let recover = if self.play.onside_expected {
+            detail!(self.utils, "The hands team is waiting for it");
+            KICKOFF.onside_recover_expected
+        } else {
+            KICKOFF.onside_recover
         };
+        mechanic!(self.utils, "Kicking team recovers up to {}", recover);
 
-        self.create_result(result_type, GAMECONSTANTS.onside_kick_line, 0)
+        let result_type = if self.utils.get_pass_num() <= recover {
+            detail!(self.utils, "Recovered by the kicking team");
+            ResultType::Regular
+        } else {
+            detail!(self.utils, "Recovered by the receiving team");
+            ResultType::TurnOver
+        };
+
+        self.create_result(result_type, 100 - (kick_line + KICKOFF.onside_distance), 0)
     }
 
-    fn run_result(&mut self, result: &KickoffResult) -> PlayResult {
-        println!("Running Result {:?}", result);
+    /// The squib is fielded short by an up man, who only gets the run number back.
+    fn run_squib_kick(&mut self, kick_line: Yard) -> PlayResult {
+        detail!(self.utils, "A squib kick bounces downfield");
+        let line = KICKOFF.squib_line + KICKOFF.kick_line - kick_line;
+        let ret_val = self.utils.get_run_num();
+        detail!(
+            self.utils,
+            format!("Fielded at the {} and returned {} yards", line, ret_val)
+        );
+
+        self.create_result(ResultType::TurnOver, line, ret_val)
+    }
+
+    fn run_out_of_bounds(&mut self, kick_line: Yard) -> PlayResult {
+        detail!(self.utils, "The kick sails out of bounds");
+        self.create_result(
+            ResultType::TurnOver,
+            100 - (kick_line + KICKOFF.out_of_bounds_distance),
+            0,
+        )
+    }
+
+    fn run_result(&mut self, result: &KickoffResult, kick_line: Yard) -> PlayResult {
+        trace!("Running Result {:?}", result);
+        // The tables' lines are for a kick from the usual spot.
+        let shift = KICKOFF.kick_line - kick_line;
 
         match result {
+            KickoffResult::Touchback if shift > 0 => {
+                detail!(self.utils, "The kick comes down short of the end zone");
+                self.run_result(
+                    &KickoffResult::Return {
+                        recipient: 1,
+                        line: 0,
+                    },
+                    kick_line,
+                )
+            }
             KickoffResult::Touchback => {
                 detail!(self.utils, "Touchback");
                 mechanic!(
@@ -74,13 +134,16 @@ impl<'a> KickPlayImpl<'a> {
             }
             KickoffResult::ColumnB => {
                 mechanic!(self.utils, "Going to Column  {}", 'B');
-                let num = self.utils.get_run_num();
-                self.run_result(KICKOFFRESULTSB.get(&num).unwrap())
+                let rn = self.utils.get_full_run_num();
+                if rn.ob {
+                    return self.run_out_of_bounds(kick_line);
+                }
+                self.run_result(KICKOFFRESULTSB.get(&rn.num).unwrap(), kick_line)
             }
             KickoffResult::Return { recipient, line } => {
                 let returner =
                     self.play.kr.as_ref().unwrap().returners[(*recipient - 1) as usize].clone();
-                self.run_return(&returner, *line)
+                self.run_return(&returner, *line + shift)
             }
         }
     }
@@ -103,7 +166,20 @@ impl<'a> KickPlayImpl<'a> {
                 );
 
                 let stats = return_stats.get_stat(self.utils.get_run_num() as usize);
-                let ret_val = self.get_return_val(stats.asterisk, stats.yards, *asterisk_val);
+                let mut ret_val = if stats.asterisk && self.is_breakaway() {
+                    *asterisk_val
+                } else {
+                    (stats.yards + self.get_coverage_effect()).max(0)
+                };
+
+                if stats.fumble {
+                    return self.run_fumble(line, ret_val);
+                }
+
+                if line + ret_val >= 100 {
+                    ret_val = 100 - line;
+                    detail!(self.utils, "He takes it all the way for a touchdown");
+                }
                 detail!(self.utils, format!("It's a {} yard return", ret_val));
 
                 self.create_result(ResultType::TurnOver, line, ret_val)
@@ -111,18 +187,60 @@ impl<'a> KickPlayImpl<'a> {
         }
     }
 
-    fn get_return_val(&mut self, is_ast: bool, card_val: Yard, ast_val: Yard) -> i32 {
-        if !is_ast {
-            return card_val;
+    /// An asterisked return breaks clear of the coverage on a run number of 1 or 2.
+    fn is_breakaway(&mut self) -> bool {
+        let breakaway = matches!(self.utils.get_run_num(), 1 | 2);
+        if breakaway {
+            detail!(self.utils, "He breaks away from the coverage");
         }
+        breakaway
+    }
 
-        match self.utils.get_run_num() {
-            1 | 2 => {
-                detail!(self.utils, "He breaks away");
-                ast_val
-            }
-            _ => card_val,
+    /// One of the kicking team's cover men gets to the returner, and his tackle rating comes
+    /// off the return (a poor tackler gives yards back).
+    fn get_coverage_effect(&mut self) -> Yard {
+        if self.play.coverage.is_empty() {
+            return 0;
         }
+
+        let num = self.utils.get_run_num() as usize;
+        let tackler = self.play.coverage[(num - 1) % self.play.coverage.len()]
+            .clone()
+            .into_base_player();
+        let tackles = PlayerUtils::get_tackles(tackler.as_ref());
+        mechanic!(self.utils, "Coverage tackles for {}", tackles);
+
+        let name = tackler.get_name();
+        let msg = match tackles.cmp(&0) {
+            Ordering::Greater => format!("{} makes the tackle to save {} yards", name, tackles),
+            Ordering::Less => format!("He slips past {} for {} more yards", name, -tackles),
+            Ordering::Equal => format!("{} makes the tackle", name),
+        };
+        detail!(self.utils, msg);
+
+        -tackles
+    }
+
+    fn run_fumble(&mut self, line: Yard, ret_val: Yard) -> PlayResult {
+        detail!(
+            self.utils,
+            format!("He fumbles after a {} yard return", ret_val)
+        );
+        mechanic!(
+            self.utils,
+            "Kicking team recovers up to {}",
+            KICKOFF.fumble_recover
+        );
+
+        let result_type = if self.utils.get_pass_num() <= KICKOFF.fumble_recover {
+            detail!(self.utils, "Recovered by the kicking team");
+            ResultType::Regular
+        } else {
+            detail!(self.utils, "The returner falls on it");
+            ResultType::TurnOver
+        };
+
+        self.create_result(result_type, line, ret_val)
     }
 
     fn create_result(&mut self, result_type: ResultType, line: Yard, result: Yard) -> PlayResult {
@@ -135,3 +253,137 @@ impl<'a> KickPlayImpl<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        engine::Down,
+        fac::FacData,
+        players::{Player, Roster},
+        testutils::{kickoff_play, pass_card, roster, roster_with, run, run_card, said, state},
+        GameTeams, PlayAndState,
+    };
+
+    /// The opening kickoff.
+    fn opening() -> GameState {
+        GameState {
+            last_status: GamePlayStatus::Start,
+            ..state()
+        }
+    }
+
+    /// The home squad kicks off from `state`, the one card dealt for the kick.
+    fn kick(
+        state: &GameState,
+        kind: KickoffKind,
+        onside_expected: bool,
+        card: FacData,
+    ) -> PlayAndState {
+        let play = kickoff_play(&roster("Home"), &roster("Away"), 0, kind, onside_expected);
+        run(state, vec![card], play).unwrap()
+    }
+
+    #[test]
+    fn test_squib_is_fielded_short_and_returned_the_run_number() {
+        let kicked = kick(&opening(), KickoffKind::Squib, false, run_card(1, 6));
+        assert!(said(&kicked.result.details, "Fielded at the 25"));
+        assert_eq!(kicked.new_state.possession, GameTeams::Away);
+        assert_eq!(kicked.new_state.yard_line, 31);
+    }
+
+    #[test]
+    fn test_onside_kick_is_harder_to_keep_when_expected() {
+        // Pass number 8 is inside the usual recovery range but not the hands team's.
+        let kept = kick(&opening(), KickoffKind::Onside, false, pass_card(1, 8));
+        assert!(said(&kept.result.details, "Recovered by the kicking team"));
+        assert_eq!(kept.new_state.possession, GameTeams::Home);
+        assert_eq!(
+            (kept.new_state.yard_line, kept.new_state.down),
+            (45, Down::First)
+        );
+
+        let lost = kick(&opening(), KickoffKind::Onside, true, pass_card(1, 8));
+        assert!(said(
+            &lost.result.details,
+            "The hands team is waiting for it"
+        ));
+        assert_eq!(lost.new_state.possession, GameTeams::Away);
+        assert_eq!(lost.new_state.yard_line, 55);
+    }
+
+    #[test]
+    fn test_free_kick_after_a_safety_comes_from_the_20() {
+        let after_safety = GameState {
+            last_status: GamePlayStatus::Safety,
+            ..state()
+        };
+        // A touchback from the 35 comes down at the 15 from the 20, and is returned.
+        let kicked = kick(&after_safety, KickoffKind::Normal, false, run_card(1, 11));
+        assert!(kicked
+            .result
+            .mechanic
+            .iter()
+            .any(|m| m == "Kicking from the 20"));
+        assert!(said(&kicked.result.details, "Kick taken by KR-1 at the 15"));
+        assert_eq!(kicked.new_state.possession, GameTeams::Away);
+        assert_eq!(kicked.new_state.yard_line, 35);
+    }
+
+    /// A normal kick returned by `receiving`, with two linebackers covering. The cards pick
+    /// the table's return (from the 3), the return itself, the tackler and, after a
+    /// fumble, who comes up with the ball.
+    fn covered_return(receiving: &Roster, tackler: i32, recovery: i32) -> PlayAndState {
+        let play = kickoff_play(&roster("Home"), receiving, 2, KickoffKind::Normal, false);
+        let cards = vec![
+            run_card(1, 5),
+            run_card(2, 3),
+            run_card(3, tackler),
+            pass_card(4, recovery),
+        ];
+        run(&opening(), cards, play).unwrap()
+    }
+
+    #[test]
+    fn test_a_cover_man_meets_the_returner() {
+        // The fixture's linebackers tackle -1, so the returner slips past for a yard more.
+        for (tackler, name) in [(1, "LB-1"), (2, "LB-2"), (3, "LB-1")] {
+            let kicked = covered_return(&roster("Away"), tackler, 1);
+            assert!(said(
+                &kicked.result.details,
+                &format!("He slips past {} for 1 more yards", name)
+            ));
+            assert!(said(&kicked.result.details, "It's a 21 yard return"));
+            assert_eq!(kicked.new_state.possession, GameTeams::Away);
+            assert_eq!(kicked.new_state.yard_line, 24);
+        }
+    }
+
+    #[test]
+    fn test_returner_fumble_goes_to_whoever_recovers() {
+        let fumbler = roster_with("Away", |p| {
+            if let Player::KR(kr) = p {
+                if let Returner::Actual { return_stats, .. } = &mut kr.returners[0] {
+                    return_stats.stats.iter_mut().for_each(|s| s.fumble = true);
+                }
+            }
+        });
+
+        let lost = covered_return(&fumbler, 1, KICKOFF.fumble_recover);
+        assert!(said(
+            &lost.result.details,
+            "He fumbles after a 21 yard return"
+        ));
+        assert!(said(&lost.result.details, "Recovered by the kicking team"));
+        assert_eq!(lost.new_state.possession, GameTeams::Home);
+        assert_eq!(
+            (lost.new_state.yard_line, lost.new_state.down),
+            (76, Down::First)
+        );
+
+        let kept = covered_return(&fumbler, 1, KICKOFF.fumble_recover + 1);
+        assert!(said(&kept.result.details, "The returner falls on it"));
+        assert_eq!(kept.new_state.possession, GameTeams::Away);
+        assert_eq!(kept.new_state.yard_line, 24);
+    }
+}
@@ -1,18 +1,21 @@
 use std::cmp::min;
 
+use spf_core::trace;
+
 use crate::{
     detail, detailf,
     game::{
         engine::{
             defs::{
-                DEFENSE_CONSTS, INTERCEPTION_RETURN_TABLE, INTERCEPTION_TABLE, PASS_DEFENDERS,
-                PASS_PLAY_VALUES, TIMES,
+                DEFENSE_CONSTS, GAMECONSTANTS, GROUNDING_YARDS, INTERCEPTION_RETURN_TABLE,
+                INTERCEPTION_TABLE, LONG_GAIN_YARDS, PASS_DEFENDERS, PASS_PLAY_VALUES, RED_ZONE,
+                TIMES,
             },
-            runplay::RunUtils,
+            runplay::{get_lg_yardage, RunUtils},
         },
         fac::{FacCard, FacData, PassTarget, ScreenResult},
         lineup::{DefensiveBox, OffensiveBox},
-        players::{Player, PlayerUtils, QBStats},
+        players::{BasePlayer, Player, PlayerUtils, QBStats},
         standard_play::{
             DefensivePlay, DefensiveStrategy, OffensivePlayInfo, OffensivePlayType,
             OffensiveStrategy, PassMetaData, PassResult, PassRushResult, PlaySetup,
@@ -20,10 +23,12 @@ use crate::{
         stats::{NumStat, RangedStats},
         GameState,
     },
-    mechanic,
+    mechanic, mechanic2,
 };
 
-use super::{defs::SCREEN_DEFENSE, playutils::PlayUtils, CardStreamer, PlayResult, ResultType};
+use super::{
+    defs::SCREEN_DEFENSE, playutils::PlayUtils, CardStreamer, PlayResult, PlayStat, ResultType,
+};
 
 pub struct PassUtils {}
 impl PassUtils {
@@ -206,11 +211,22 @@ impl<'a> PassContext<'a> {
         let qb = PassContext::get_qb_stats(&self.play);
 
         let shift = self.calculate_pass_shift();
+        let receiver = self.get_exceptional_receiver_effect();
 
         let range = (self.data.md.completion_range)(&qb);
         mechanic!(self.utils, "Pre Shift Completion Range: {:?}", range);
-        let res = range.get_category(self.utils.get_pass_num(), shift);
+        let pass_num = self.utils.get_pass_num();
+        let res = range.get_category(pass_num, shift + receiver);
         mechanic!(self.utils, "Pass Result: {:?} ", res);
+        if receiver != 0 && res != range.get_category(pass_num, shift) {
+            detail!(self.utils, "An exceptional receiver gets open");
+        }
+
+        if res != PassResult::Interception {
+            if let Some(defender) = self.get_coverage_interceptor(pass_num) {
+                return self.coverage_interception(defender);
+            }
+        }
 
         match res {
             PassResult::Complete => self.complete_pass(),
@@ -223,7 +239,13 @@ impl<'a> PassContext<'a> {
         let off_block = self.get_offensive_block();
         let def_rush = self.get_defensive_rush();
 
-        let sack_range_impact = (def_rush - off_block) * 2;
+        // The line-vs-rush rating comparison is an advanced-rules mechanic; the basic game
+        // reads the QB's pass rush range as printed.
+        let sack_range_impact = if self.state.rules.is_advanced() {
+            (def_rush - off_block) * 2
+        } else {
+            0
+        };
         mechanic!(self.utils, "Sack range impact of {}", sack_range_impact);
 
         let qb = PassContext::get_qb_stats(&self.play);
@@ -239,24 +261,77 @@ impl<'a> PassContext<'a> {
         }
     }
 
+    /// Sacked behind the line, never further back than his own goal line; dragged down
+    /// there it's a safety.
     fn sack(&mut self) -> PlayResult {
-        let yds = self.utils.get_pass_num() / 3;
+        if self.play.offense_call.throw_away {
+            return self.intentional_grounding();
+        }
+
+        let yds = min(self.utils.get_pass_num() / 3, self.state.yard_line);
         detail!(self.utils, format!("The QB is sacked for {} yards", yds));
+        if yds == self.state.yard_line {
+            detail!(self.utils, "He goes down in the end zone for a safety");
+        }
+        let passer = self.passer_id();
+        self.utils.record_stat(PlayStat::Sack {
+            passer,
+            yards: -yds,
+        });
 
         self.create_result(-yds, ResultType::Regular, TIMES.run_play)
     }
 
+    /// The QB throws it away rather than go down: an incomplete pass and a penalty,
+    /// half the distance to the goal when that is closer. Half the distance rounds up, so
+    /// backed up to his own 1 he's throwing from the end zone and it's a safety.
+    fn intentional_grounding(&mut self) -> PlayResult {
+        let yds = min(GROUNDING_YARDS, (self.state.yard_line + 1) / 2);
+        detail!(
+            self.utils,
+            format!(
+                "The QB throws it away, intentional grounding costs {} yards",
+                yds
+            )
+        );
+        if yds == self.state.yard_line {
+            detail!(self.utils, "He threw it from the end zone, a safety");
+        }
+        let passer = self.passer_id();
+        self.utils.record_stat(PlayStat::Pass {
+            passer,
+            receiver: None,
+            complete: false,
+            yards: 0,
+            touchdown: false,
+        });
+
+        self.create_result(-yds, ResultType::Regular, TIMES.pass_play_incomplete)
+    }
+
+    /// The QB scrambles off his rushing column, stopping the clock if the run number
+    /// puts him out of bounds.
     fn qb_run(&mut self) -> PlayResult {
         let qb = PassContext::get_qb_stats(&self.play);
-        let val = qb.rushing.get_stat(self.utils.get_run_num() as usize);
-        let yds = match val {
-            NumStat::Sg => 0,
-            NumStat::Lg => 0,
-            NumStat::Val(v) => *v,
+        let rn = self.utils.get_full_run_num();
+        let (yds, ob) = match qb.rushing.get_stat(rn.num as usize) {
+            NumStat::Sg => RunUtils::calculate_sg_yardage(&mut self.utils),
+            NumStat::Lg => (get_lg_yardage(qb.long_run), false),
+            NumStat::Val(v) => (*v, rn.ob),
         };
         detail!(self.utils, format!("The QB runs for it for {} yards", yds));
-
-        self.create_result(yds, ResultType::Regular, TIMES.run_play)
+        self.utils.record_stat(PlayStat::Rush {
+            player: qb.id.clone(),
+            yards: yds,
+        });
+
+        let time = if ob {
+            detail!(self.utils, "He gets out of bounds");
+            TIMES.run_play_ob
+        } else {
+            TIMES.run_play
+        };
+        self.create_result(yds, ResultType::Regular, time)
     }
 
     fn complete_pass(&mut self) -> PlayResult {
@@ -275,6 +350,7 @@ impl<'a> PassContext<'a> {
 
     fn incomplete_pass(&mut self) -> PlayResult {
         detail!(self.utils, "The pass falls incomplete");
+        self.record_pass(false, 0, false);
 
         self.create_result(0, ResultType::Regular, TIMES.pass_play_incomplete)
     }
@@ -285,12 +361,26 @@ impl<'a> PassContext<'a> {
 
     fn long_gain(&mut self) -> PlayResult {
         detail!(self.utils, "It's a long gain");
-        let yards = min(30, self.utils.get_run_num() * 4);
+        let mut yards = min(LONG_GAIN_YARDS, self.utils.get_run_num() * 4);
+        if let Some(lg) = self.get_receiving_lg() {
+            mechanic!(self.utils, "Receiver long gain rating: {}", lg);
+            yards = yards.max(get_lg_yardage(lg));
+        }
         self.finalize_pass(yards)
     }
 
+    /// The field ends at the goal line: a catch that gets there is a touchdown for the
+    /// yards that were left, however far the card says he'd have gone.
     fn finalize_pass(&mut self, yards: i32) -> PlayResult {
+        let yards = self.utils.get_big_play_yards(self.play.big_play, yards);
+        let to_goal = self.state.yards_to_goal();
+        let touchdown = yards >= to_goal;
+        let yards = min(yards, to_goal);
         detail!(self.utils, format!("Pass complete for {} yards", yards));
+        if touchdown {
+            detail!(self.utils, "Touchdown!");
+        }
+        self.record_pass(true, yards, touchdown);
 
         self.create_result(yards, ResultType::Regular, TIMES.pass_play_complete)
     }
@@ -316,8 +406,44 @@ impl<'a> PassContext<'a> {
             return self.incomplete_pass();
         }
 
-        let ret_yards = self.get_return_yardage(players[0].get_pos().to_string());
+        let defender = players[0].get_id();
+        let pos = players[0].get_pos().to_string();
+        self.finish_interception(defender, pos, int_point)
+    }
+
+    /// The keyed defender reads the throw: his own interception range, checked against the
+    /// pass number, picks it off whatever the QB's card said.
+    fn coverage_interception(&mut self, defender: Box<dyn BasePlayer>) -> PlayResult {
+        let int_point = self.get_interception_point();
+        detail!(
+            self.utils,
+            format!(
+                "{} jumps the route and picks it off, {} yards downfield",
+                defender.get_name(),
+                int_point
+            )
+        );
 
+        self.finish_interception(defender.get_id(), defender.get_pos().to_string(), int_point)
+    }
+
+    fn finish_interception(&mut self, defender: String, pos: String, int_point: i32) -> PlayResult {
+        let passer = self.passer_id();
+        self.utils
+            .record_stat(PlayStat::Interception { passer, defender });
+
+        // Picked off in the end zone: a touchback, and the ball comes out to the 20.
+        let to_goal = self.state.yards_to_goal();
+        if int_point >= to_goal {
+            detail!(self.utils, "Caught in the end zone for a touchback");
+            return self.create_result(
+                to_goal - GAMECONSTANTS.touchback_line,
+                ResultType::TurnOver,
+                TIMES.pass_play_complete,
+            );
+        }
+
+        let ret_yards = self.get_return_yardage(pos);
         self.create_result(
             int_point - ret_yards,
             ResultType::TurnOver,
@@ -325,6 +451,53 @@ impl<'a> PassContext<'a> {
         )
     }
 
+    /// Under the advanced rules, a defense keying the receiver the ball goes to puts its
+    /// cover man's interception range in play.
+    fn get_coverage_interceptor(&mut self, pass_num: i32) -> Option<Box<dyn BasePlayer>> {
+        if !self.state.rules.is_advanced() || self.play.defense_call.key != Some(self.data.target) {
+            return None;
+        }
+
+        let def_box = PASS_DEFENDERS.get(&self.data.target)?;
+        let defender = self
+            .play
+            .defense
+            .get_players_in_pos(def_box)
+            .into_iter()
+            .find(|p| PlayerUtils::get_intercepts(*p).is_some_and(|r| r.in_range(pass_num)))
+            .map(|p| p.get_full_player().into_base_player());
+
+        if let Some(d) = &defender {
+            mechanic2!(
+                self.utils,
+                "Pass num {} is in {}'s interception range",
+                pass_num,
+                d.get_name()
+            );
+        }
+        defender
+    }
+
+    fn passer_id(&self) -> String {
+        PassContext::get_qb_stats(&self.play).id
+    }
+
+    fn record_pass(&mut self, complete: bool, yards: i32, touchdown: bool) {
+        let receiver = self
+            .play
+            .offense
+            .get_player_in_pos(&self.data.target)
+            .map(|p| p.get_id());
+        let passer = self.passer_id();
+        self.utils.record_stat(PlayStat::Pass {
+            passer,
+            receiver,
+            complete,
+            yards,
+            touchdown,
+        });
+    }
+
     fn calculate_pass_shift(&mut self) -> i32 {
         let def_impact = self.get_def_impact();
 
@@ -335,7 +508,9 @@ impl<'a> PassContext<'a> {
 
         let playaction = self.get_play_action_effect();
 
-        let shift = val + playaction;
+        let red_zone = self.get_red_zone_effect();
+
+        let shift = val + playaction + red_zone;
         mechanic!(self.utils, "Pass Shift: {}", shift);
 
         return shift;
@@ -373,7 +548,7 @@ impl<'a> PassContext<'a> {
     }
 
     fn get_pass_gain(&mut self) -> Option<NumStat> {
-        println!("Target is {:?}", self.data.target);
+        trace!("Target is {:?}", self.data.target);
         let pass_gain =
             PlayerUtils::get_pass_gain(self.play.offense.get_player_in_pos(&self.data.target))
                 .unwrap();
@@ -421,7 +596,7 @@ impl<'a> PassContext<'a> {
     }
 
     fn get_pass_defender_impact(&mut self) -> i32 {
-        if self.play.defense_call.key == Some(self.data.target) {
+        if self.state.rules.is_advanced() && self.play.defense_call.key == Some(self.data.target) {
             match self.play.defense_call.strategy {
                 DefensiveStrategy::DoubleCover => return DEFENSE_CONSTS.double_cover_defense,
                 DefensiveStrategy::TripleCover => return DEFENSE_CONSTS.triple_cover_defense,
@@ -450,7 +625,8 @@ impl<'a> PassContext<'a> {
     }
 
     fn get_play_action_effect(&mut self) -> i32 {
-        if self.play.offense_call.strategy != OffensiveStrategy::PlayAction
+        if !self.state.rules.is_advanced()
+            || self.play.offense_call.strategy != OffensiveStrategy::PlayAction
             || (self.play.offense_call.play_type != OffensivePlayType::SH
                 && self.play.offense_call.play_type != OffensivePlayType::LG)
         {
@@ -474,6 +650,50 @@ impl<'a> PassContext<'a> {
         pa_effect
     }
 
+    fn get_receiving_lg(&self) -> Option<char> {
+        self.play
+            .offense
+            .get_player_in_pos(&self.data.target)
+            .and_then(PlayerUtils::get_receiving_lg)
+    }
+
+    /// Under the advanced rules, a receiver whose long-gain letter goes further than any long
+    /// gain off the card is open more often.
+    fn get_exceptional_receiver_effect(&mut self) -> i32 {
+        if !self.state.rules.is_advanced()
+            || self
+                .get_receiving_lg()
+                .is_none_or(|lg| get_lg_yardage(lg) <= LONG_GAIN_YARDS)
+        {
+            return 0;
+        }
+
+        mechanic!(
+            self.utils,
+            "Exceptional receiver effect: {}",
+            PASS_PLAY_VALUES.exceptional_receiver
+        );
+        PASS_PLAY_VALUES.exceptional_receiver
+    }
+
+    fn get_red_zone_effect(&mut self) -> i32 {
+        if self.state.yards_to_goal() > RED_ZONE.start {
+            return 0;
+        }
+
+        let effect = match self.play.offense_call.play_type {
+            OffensivePlayType::QK => RED_ZONE.qk_defense,
+            OffensivePlayType::SH => RED_ZONE.sh_defense,
+            _ => 0,
+        };
+        if effect == 0 {
+            return 0;
+        }
+        detail!(self.utils, "The defense has less field to cover");
+        mechanic!(self.utils, "Red zone effect: {}", effect);
+        effect
+    }
+
     fn get_offensive_block(&mut self) -> i32 {
         let blockers = vec![
             OffensiveBox::LT,
@@ -547,3 +767,308 @@ impl<'a> PassContext<'a> {
             && self.play.defense_call.def_players.contains(&player)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        fac::RunNum,
+        players::Roster,
+        standard_play::{StandardDefenseCall, StandardOffenseCall},
+        stats::Range,
+        testutils::{
+            card, defense_call, offense_call, pass_card, roster, roster_with, run, said,
+            standard_play, state,
+        },
+        RulesLevel,
+    };
+
+    /// A short pass to the flanker against a straight pass defense (a -5 shift, so passes
+    /// complete on 1-25), thrown on `pass_num`.
+    fn short_pass(
+        offense: &Roster,
+        defense: &Roster,
+        def_call: StandardDefenseCall,
+        state: &GameState,
+        pass_num: i32,
+    ) -> PlayResult {
+        let play = standard_play(
+            offense,
+            defense,
+            offense_call(OffensivePlayType::SH, OffensiveBox::FL1),
+            def_call,
+        );
+        run(state, vec![card(1), pass_card(2, pass_num)], play)
+            .unwrap()
+            .result
+    }
+
+    /// The home squad with the flanker's long-gain letter set to `lg`.
+    fn flanker_rated(lg: char) -> Roster {
+        roster_with("Home", |p| {
+            if let Player::WR(wr) = p {
+                if wr.id == "WR-1" {
+                    wr.lg = lg;
+                }
+            }
+        })
+    }
+
+    fn basic() -> GameState {
+        GameState {
+            rules: RulesLevel::Basic,
+            ..state()
+        }
+    }
+
+    #[test]
+    fn test_exceptional_receiver_gets_open_when_it_matters() {
+        let pass_defense = || defense_call(DefensivePlay::PassDefense);
+        let star = flanker_rated('B');
+        let caught = short_pass(&star, &roster("Away"), pass_defense(), &state(), 27);
+        assert!(matches!(
+            caught.stat,
+            Some(PlayStat::Pass { complete: true, .. })
+        ));
+        assert!(said(&caught.details, "An exceptional receiver gets open"));
+
+        // A catch he'd have made anyway isn't his doing.
+        let routine = short_pass(&star, &roster("Away"), pass_defense(), &state(), 20);
+        assert!(matches!(
+            routine.stat,
+            Some(PlayStat::Pass { complete: true, .. })
+        ));
+        assert!(!said(&routine.details, "exceptional"));
+
+        // A letter no better than the card's own long gain, or the basic rules, get nothing.
+        for (offense, state) in [(flanker_rated('O'), state()), (star, basic())] {
+            let dropped = short_pass(&offense, &roster("Away"), pass_defense(), &state, 27);
+            assert!(matches!(
+                dropped.stat,
+                Some(PlayStat::Pass {
+                    complete: false,
+                    ..
+                })
+            ));
+            assert!(!said(&dropped.details, "exceptional"));
+        }
+    }
+
+    #[test]
+    fn test_keyed_ball_hawk_jumps_the_route() {
+        let hawks = roster_with("Away", |p| {
+            if let Player::DB(db) = p {
+                db.intercepts = Range { start: 15, end: 25 };
+            }
+        });
+        let keyed = || StandardDefenseCall {
+            key: Some(OffensiveBox::FL1),
+            ..defense_call(DefensivePlay::PassDefense)
+        };
+
+        let picked = short_pass(&roster("Home"), &hawks, keyed(), &state(), 20);
+        assert!(matches!(picked.result_type, ResultType::TurnOver));
+        assert!(said(&picked.details, "jumps the route"));
+        assert!(matches!(
+            &picked.stat,
+            Some(PlayStat::Interception { defender, .. }) if defender.starts_with("DB")
+        ));
+
+        // Out of his range, unkeyed, or under the basic rules, it's just a catch.
+        for (pass_num, def_call, state) in [
+            (10, keyed(), state()),
+            (20, defense_call(DefensivePlay::PassDefense), state()),
+            (20, keyed(), basic()),
+        ] {
+            let result = short_pass(&roster("Home"), &hawks, def_call, &state, pass_num);
+            assert!(matches!(
+                result.stat,
+                Some(PlayStat::Pass { complete: true, .. })
+            ));
+        }
+    }
+
+    /// First and goal from the 5.
+    fn goal_to_go() -> GameState {
+        GameState {
+            yard_line: 95,
+            first_down_target: 100,
+            ..state()
+        }
+    }
+
+    #[test]
+    fn test_no_long_pass_inside_the_red_zone() {
+        let play = standard_play(
+            &roster("Home"),
+            &roster("Away"),
+            offense_call(OffensivePlayType::LG, OffensiveBox::FL1),
+            defense_call(DefensivePlay::PassDefense),
+        );
+        let err = run(&goal_to_go(), vec![card(1)], play).unwrap_err();
+        assert_eq!(err.code(), "invalid_call");
+    }
+
+    #[test]
+    fn test_red_zone_coverage_tightens_short_passes() {
+        let pass_defense = || defense_call(DefensivePlay::PassDefense);
+        // Complete on 1-25 in the open field, 1-20 once the field shrinks.
+        let open = short_pass(
+            &roster("Home"),
+            &roster("Away"),
+            pass_defense(),
+            &state(),
+            22,
+        );
+        assert!(matches!(
+            open.stat,
+            Some(PlayStat::Pass { complete: true, .. })
+        ));
+        assert!(!said(&open.details, "less field to cover"));
+
+        let tight = short_pass(
+            &roster("Home"),
+            &roster("Away"),
+            pass_defense(),
+            &goal_to_go(),
+            22,
+        );
+        assert!(matches!(
+            tight.stat,
+            Some(PlayStat::Pass {
+                complete: false,
+                ..
+            })
+        ));
+        assert!(said(&tight.details, "The defense has less field to cover"));
+    }
+
+    #[test]
+    fn test_a_catch_stops_at_the_goal_line() {
+        // The card's 10-yard short gain only has 5 yards of field left.
+        let result = short_pass(
+            &roster("Home"),
+            &roster("Away"),
+            defense_call(DefensivePlay::PassDefense),
+            &goal_to_go(),
+            1,
+        );
+        assert_eq!(result.result, 5);
+        assert!(matches!(
+            result.stat,
+            Some(PlayStat::Pass {
+                complete: true,
+                yards: 5,
+                touchdown: true,
+                ..
+            })
+        ));
+        assert!(said(&result.details, "Touchdown!"));
+    }
+
+    /// A short pass from `yard_line` where the pass rush gets in. `rush_num` reads the QB's
+    /// pass rush line and `next` is the card for the sack or the scramble.
+    fn rushed(yard_line: i32, throw_away: bool, rush_num: i32, next: FacData) -> PlayResult {
+        let play = standard_play(
+            &roster("Home"),
+            &roster("Away"),
+            StandardOffenseCall {
+                throw_away,
+                ..offense_call(OffensivePlayType::SH, OffensiveBox::FL1)
+            },
+            defense_call(DefensivePlay::PassDefense),
+        );
+        let backed_up = GameState {
+            yard_line,
+            first_down_target: yard_line + 10,
+            ..state()
+        };
+        let rush = FacData {
+            sh: PassTarget::PassRush,
+            ..card(1)
+        };
+        // A -2 shift on the pass rush line: sacked on 1-10, scrambles on 11-22.
+        run(&backed_up, vec![rush, pass_card(2, rush_num), next], play)
+            .unwrap()
+            .result
+    }
+
+    #[test]
+    fn test_sacked_in_the_end_zone_is_a_safety() {
+        let result = rushed(4, false, 5, pass_card(3, 30));
+        assert_eq!((result.result, result.final_line), (-4, 0));
+        assert!(matches!(
+            result.stat,
+            Some(PlayStat::Sack { yards: -4, .. })
+        ));
+        assert!(said(
+            &result.details,
+            "He goes down in the end zone for a safety"
+        ));
+    }
+
+    #[test]
+    fn test_grounding_costs_half_the_distance_but_never_nothing() {
+        for (yard_line, penalty) in [(50, -GROUNDING_YARDS), (4, -2), (3, -2), (1, -1)] {
+            let result = rushed(yard_line, true, 5, card(3));
+            assert_eq!(
+                (result.result, result.time),
+                (penalty, TIMES.pass_play_incomplete)
+            );
+            assert!(matches!(
+                result.stat,
+                Some(PlayStat::Pass {
+                    complete: false,
+                    ..
+                })
+            ));
+            // Only from the 1 is he throwing out of his own end zone.
+            assert_eq!(
+                said(&result.details, "a safety"),
+                yard_line == 1,
+                "{:?}",
+                result.details
+            );
+        }
+    }
+
+    #[test]
+    fn test_scramble_out_of_bounds_stops_the_clock() {
+        let run_card = |ob| FacData {
+            run_num: RunNum { num: 1, ob },
+            ..card(3)
+        };
+        let inbounds = rushed(50, false, 15, run_card(false));
+        assert!(matches!(
+            &inbounds.stat,
+            Some(PlayStat::Rush { player, yards: 4 }) if player == "QB-1"
+        ));
+        assert_eq!(inbounds.time, TIMES.run_play);
+
+        let out = rushed(50, false, 15, run_card(true));
+        assert_eq!(out.time, TIMES.run_play_ob);
+        assert!(said(&out.details, "He gets out of bounds"));
+    }
+
+    #[test]
+    fn test_basic_rules_read_the_pass_rush_as_printed() {
+        let rushed_under = |state: GameState| {
+            let play = standard_play(
+                &roster("Home"),
+                &roster("Away"),
+                offense_call(OffensivePlayType::SH, OffensiveBox::FL1),
+                defense_call(DefensivePlay::PassDefense),
+            );
+            let rush = FacData {
+                sh: PassTarget::PassRush,
+                ..card(1)
+            };
+            let cards = vec![rush, pass_card(2, 30), card(3), card(4)];
+            run(&state, cards, play).unwrap().result
+        };
+        let advanced = rushed_under(state());
+        assert!(said(&advanced.mechanic, "Sack range impact of -2"));
+        let basic = rushed_under(basic());
+        assert!(said(&basic.mechanic, "Sack range impact of 0"));
+    }
+}
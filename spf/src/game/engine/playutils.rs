@@ -1,9 +1,11 @@
+use std::cmp::{max, min};
+
 use crate::game::{
     fac::{FacData, RunNum},
     GameState,
 };
 
-use super::{CardStreamer, PlayResult};
+use super::{defs::BIG_PLAY, CardStreamer, PlayResult, PlayStat, Yard};
 
 // use macro_rules! <name of macro> {<Body>}
 #[macro_export]
@@ -49,9 +51,9 @@ macro_rules! detailf {
 pub struct PlayUtils<'a> {
     details: Vec<String>,
     mechanics: Vec<String>,
+    stat: Option<PlayStat>,
+    big_play_tried: bool,
     cards: &'a mut CardStreamer<'a>,
-    // unused: stored by the constructor but never read (state access moved elsewhere); kept pending removal.
-    #[allow(dead_code)]
     state: &'a GameState,
 }
 
@@ -60,6 +62,8 @@ impl<'a> PlayUtils<'a> {
         Self {
             details: vec![],
             mechanics: vec![],
+            stat: None,
+            big_play_tried: false,
             cards,
             state,
         }
@@ -97,12 +101,40 @@ impl<'a> PlayUtils<'a> {
         self.details.push(msg);
     }
 
+    /// The offense's one big-play chance of the drive, taken on its first gain. A big play
+    /// multiplies the gain, though never past the goal line it didn't already reach.
+    pub fn get_big_play_yards(&mut self, rating: i32, yards: Yard) -> Yard {
+        if !self.state.rules.is_advanced() || self.state.big_play_tried || rating <= 0 || yards <= 0
+        {
+            return yards;
+        }
+        self.big_play_tried = true;
+
+        let chance = rating * BIG_PLAY.chance_per_point;
+        mechanic!(self, "Big play on {} or less", chance);
+        if self.get_pass_num() > chance {
+            return yards;
+        }
+
+        detail!(self, "It's a big play!");
+        min(
+            yards * BIG_PLAY.multiplier,
+            max(yards, self.state.yards_to_goal()),
+        )
+    }
+
+    pub fn record_stat(&mut self, stat: PlayStat) {
+        self.stat = Some(stat);
+    }
+
     pub fn result(&self) -> PlayResult {
         PlayResult {
             details: self.details.clone(),
             mechanic: self.mechanics.clone(),
             extra: None,
             cards: self.cards.get_results(),
+            stat: self.stat.clone(),
+            big_play_tried: self.big_play_tried,
 
             result_type: super::ResultType::Regular,
             result: 0,
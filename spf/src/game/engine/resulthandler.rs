@@ -14,6 +14,7 @@ pub fn calculate_play_result(old_state: &GameState, result: &PlayResult) -> Game
         time_remaining,
         quarter,
         play_counter: old_state.play_counter + 1,
+        big_play_tried: old_state.big_play_tried || result.big_play_tried,
 
         ..old_state.clone()
     };
@@ -29,10 +30,22 @@ fn handle_regular_play(interim_state: &GameState, _result: &PlayResult) -> GameS
         return handle_touchdown(interim_state);
     }
 
-    if interim_state.yard_line < 0 {
+    // The goal line is part of the end zone: a ball carrier downed on it is in for a safety.
+    if interim_state.yard_line <= 0 {
         return handle_safety(interim_state);
     }
 
+    // Only a kick follows these; one the kicking team keeps is a fresh set of downs.
+    if matches!(
+        interim_state.last_status,
+        GamePlayStatus::Start
+            | GamePlayStatus::Touchdown
+            | GamePlayStatus::FieldGoal
+            | GamePlayStatus::Safety
+    ) {
+        return first_down(interim_state);
+    }
+
     if interim_state.yard_line >= interim_state.first_down_target {
         return first_down(&interim_state);
     }
@@ -59,12 +72,14 @@ fn handle_safety(interim_state: &GameState) -> GameState {
         last_status: GamePlayStatus::Safety,
         home_score,
         away_score,
+        big_play_tried: false,
         ..interim_state.clone()
     }
 }
 
 fn handle_turnover(interim_state: &GameState) -> GameState {
-    if interim_state.yard_line < 0 {
+    // Likewise a defender who takes the ball to the goal line has scored.
+    if interim_state.yard_line <= 0 {
         let score_state = GameState {
             possession: interim_state.possession.other_team(),
 
@@ -83,6 +98,7 @@ fn handle_touchdown(interim_state: &GameState) -> GameState {
         last_status: GamePlayStatus::Touchdown,
         home_score,
         away_score,
+        big_play_tried: false,
         ..interim_state.clone()
     }
 }
@@ -105,6 +121,7 @@ fn possession_change(interim_state: &GameState) -> GameState {
         first_down_target: min(yard_line + 10, 100),
         possession: interim_state.possession.other_team(),
         yard_line,
+        big_play_tried: false,
 
         ..interim_state.clone()
     }
@@ -138,9 +155,9 @@ mod tests {
     //
     // `GameState` / `PlayResult` are plain public-field structs, so tests build
     // them via struct-update from a known baseline rather than a builder type.
-    // `GamePlayStatus` and `GameTeams` do not derive `PartialEq`, so status /
-    // possession are compared by discriminant via the `is_status` / `is_possession`
-    // helpers below.
+    // `GamePlayStatus` does not derive `PartialEq`, so status is compared by
+    // discriminant via the `is_status` helper below (`is_possession` predates
+    // `GameTeams: PartialEq` and is kept for symmetry).
 
     /// A mid-drive baseline: Away has the ball, 2nd & (target 60) at midfield,
     /// early in Q1 with plenty of time on the clock.
@@ -149,6 +166,7 @@ mod tests {
             down: Down::Second,
             yard_line: 50,
             first_down_target: 60,
+            last_status: GamePlayStatus::Ongoing,
             ..GameState::start_state()
         }
     }
@@ -165,6 +183,8 @@ mod tests {
             mechanic: vec![],
             extra: None,
             cards: CardResults::default(),
+            stat: None,
+            big_play_tried: false,
         }
     }
 
@@ -257,7 +277,7 @@ mod tests {
             down: Down::Fourth,
             yard_line: 55,
             first_down_target: 60,
-            ..GameState::start_state()
+            ..base_state()
         };
         let new_state = calculate_play_result(&state, &play_result(ResultType::Regular, 57, 15));
 
@@ -338,6 +358,25 @@ mod tests {
         assert_eq!(new_state.away_score, 0);
     }
 
+    #[test]
+    fn test_turnover_on_the_goal_line_is_defensive_touchdown() {
+        // The goal line counts as the end zone, for the defense as for a safety.
+        let state = base_state();
+        let new_state = calculate_play_result(&state, &play_result(ResultType::TurnOver, 0, 15));
+
+        assert!(is_status(&new_state, GamePlayStatus::Touchdown));
+        assert_eq!(new_state.home_score, 6);
+    }
+
+    #[test]
+    fn test_turnover_a_yard_short_of_the_goal_line_is_not_a_score() {
+        let state = base_state();
+        let new_state = calculate_play_result(&state, &play_result(ResultType::TurnOver, 1, 15));
+
+        assert!(is_status(&new_state, GamePlayStatus::PossessionChange));
+        assert_eq!((new_state.home_score, new_state.away_score), (0, 0));
+    }
+
     #[test]
     fn test_regular_play_behind_own_goal_line_is_safety() {
         // Regular play, ball driven behind the goal line (< 0) -> safety,
@@ -353,6 +392,54 @@ mod tests {
         assert_eq!(new_state.away_score, 0);
     }
 
+    #[test]
+    fn test_regular_play_ending_on_own_goal_line_is_safety() {
+        let state = base_state();
+        let new_state = calculate_play_result(&state, &play_result(ResultType::Regular, 0, 15));
+
+        assert!(is_status(&new_state, GamePlayStatus::Safety));
+        assert_eq!(new_state.home_score, 2);
+    }
+
+    #[test]
+    fn test_regular_play_ending_a_yard_out_is_not_safety() {
+        let state = base_state();
+        let new_state = calculate_play_result(&state, &play_result(ResultType::Regular, 1, 15));
+
+        assert!(is_status(&new_state, GamePlayStatus::Ongoing));
+        assert_eq!((new_state.yard_line, new_state.home_score), (1, 0));
+    }
+
+    #[test]
+    fn test_kick_kept_by_kicking_team_is_first_down() {
+        // An onside kick recovered by the kicking team: no down to advance from.
+        let state = GameState {
+            last_status: GamePlayStatus::Touchdown,
+            ..base_state()
+        };
+        let new_state = calculate_play_result(&state, &play_result(ResultType::Regular, 45, 10));
+
+        assert!(is_status(&new_state, GamePlayStatus::Ongoing));
+        assert_eq!(new_state.down, Down::First);
+        assert_eq!(new_state.first_down_target, 55);
+    }
+
+    #[test]
+    fn test_big_play_chance_lasts_until_the_drive_ends() {
+        let tried = PlayResult {
+            big_play_tried: true,
+            ..play_result(ResultType::Regular, 52, 40)
+        };
+        let state = calculate_play_result(&base_state(), &tried);
+        assert!(state.big_play_tried);
+
+        let state = calculate_play_result(&state, &play_result(ResultType::Regular, 55, 40));
+        assert!(state.big_play_tried, "still the same drive");
+
+        let state = calculate_play_result(&state, &play_result(ResultType::TurnOver, 55, 10));
+        assert!(!state.big_play_tried, "a new drive gets a new chance");
+    }
+
     // ---- clock rollover (advance_time via the public entry point) ----------
 
     #[test]
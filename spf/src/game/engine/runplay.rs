@@ -3,22 +3,23 @@ use std::cmp::{max, min};
 use crate::{
     detail, detailf,
     game::{
-        engine::defs::{DRAW_IMPACT, RUN_DEFENSE, TIMES},
-        fac::{FacCard, FacData, RunDirection, RunDirectionActual},
+        engine::defs::{DRAW_IMPACT, QB_SNEAK, RUN_DEFENSE, TIMES},
+        fac::{FacCard, FacData, RunBlocker, RunDirection, RunDirectionActual},
         lineup::{DefensiveBox, OffensiveBox},
-        players::{BasePlayer, Player, PlayerUtils, RBStats},
+        players::{Player, PlayerUtils, RBStats},
         standard_play::{
             DefensivePlay, OffensivePlayInfo, OffensivePlayType, OffensiveStrategy, PlaySetup,
             RunMetaData,
         },
-        stats::{self, NumStat},
+        stats::{self, NumStat, TwelveStats},
         GameState,
     },
     mechanic, mechanic2,
 };
 
 use super::{
-    defs::RunPlayDefenseImpact, playutils::PlayUtils, CardStreamer, PlayResult, ResultType,
+    defs::RunPlayDefenseImpact, playutils::PlayUtils, CardStreamer, PlayResult, PlayStat,
+    ResultType,
 };
 
 pub struct RunUtils {}
@@ -51,6 +52,34 @@ impl RunUtils {
         &card.ir
     }
 
+    /// The ball carrier called for the play.
+    pub fn get_runner(play: &PlaySetup) -> Runner {
+        let player = play
+            .offense
+            .get_player_in_pos(&play.offense_call.target)
+            .unwrap()
+            .get_full_player();
+        match player {
+            Player::QB(qb) => Runner {
+                id: qb.id,
+                name: qb.name,
+                lg: qb.long_run,
+                rushing: qb.rushing,
+            },
+            other => {
+                let rb = Player::is_rb(other).unwrap();
+                Runner {
+                    rushing: TwelveStats {
+                        stats: (1..=12).map(|n| *RunUtils::get_rush_stat(&rb, n)).collect(),
+                    },
+                    id: rb.id,
+                    name: rb.name,
+                    lg: rb.lg,
+                }
+            }
+        }
+    }
+
     pub fn get_rb_stats(play: &PlaySetup) -> RBStats {
         let player = play
             .offense
@@ -96,6 +125,12 @@ impl RunUtils {
         mechanic!(utils, "Run modifier {}", modifier);
         return modifier;
     }
+    /// Short gain: the runner is already through the line. A new FAC is flipped and he
+    /// gains its run number plus 5, out of bounds if that card's run number is.
+    ///
+    /// This stands in for the card rule, which reads the new run number in the ball
+    /// carrier's SG column. The converted cards only carry the N column so far (see the
+    /// "Short Gain Rules" item in `Backlog.md`).
     pub fn calculate_sg_yardage(utils: &mut PlayUtils) -> (i32, bool) {
         detail!(utils, "He gets out for short gain");
         let rn = utils.get_full_run_num();
@@ -103,6 +138,21 @@ impl RunUtils {
     }
 }
 
+/// The ball carrier's numbers: a back runs off the "N" line of his rushing column, a
+/// quarterback off his own rushing column, each with his long-gain rating.
+pub struct Runner {
+    pub id: String,
+    pub name: String,
+    pub lg: char,
+    rushing: TwelveStats<NumStat>,
+}
+
+impl Runner {
+    pub fn rush_stat(&self, run_num: i32) -> NumStat {
+        *self.rushing.get_stat(run_num.try_into().unwrap())
+    }
+}
+
 // #[derive(Clone)]
 pub struct RunPlayData {
     yardage: i32,
@@ -131,9 +181,20 @@ struct RunContext<'a> {
 
 impl<'a> RunContext<'a> {
     fn start_run(&mut self) -> PlayResult {
-        let player = RunUtils::get_rb_stats(&self.play);
+        let runner = RunUtils::get_runner(&self.play);
 
-        detail!(self.utils, format!("Handoff to {}", player.get_name()));
+        if self.play.offense_call.strategy == OffensiveStrategy::Sneak {
+            detail!(
+                self.utils,
+                format!("{} sneaks behind the center", runner.name)
+            );
+            return self.handle_sneak();
+        }
+        if self.play.offense_call.target == OffensiveBox::QB {
+            detail!(self.utils, format!("{} keeps it", runner.name));
+        } else {
+            detail!(self.utils, format!("Handoff to {}", runner.name));
+        }
 
         let dir = self.get_run_direction();
         match dir {
@@ -144,11 +205,47 @@ impl<'a> RunContext<'a> {
 
     fn handle_breakaway(&mut self) -> PlayResult {
         detail!(self.utils, "It's a breakaway");
-        let rb = RunUtils::get_rb_stats(&self.play);
-        self.data.yardage = get_lg_yardage(rb.lg);
+        self.handle_long_gain()
+    }
+
+    /// Long gain: yardage from the runner's LG rating, as on a breakaway.
+    fn handle_long_gain(&mut self) -> PlayResult {
+        let runner = RunUtils::get_runner(&self.play);
+        self.data.yardage = get_lg_yardage(runner.lg);
         return self.finalize_yardage();
     }
 
+    /// QB sneak: the quarterback's rushing column behind the interior blocking on the
+    /// FAC's inside result, held to a short push either way and never out of bounds.
+    fn handle_sneak(&mut self) -> PlayResult {
+        let yardage = match self.get_run_direction() {
+            RunDirection::Actual(actual) => {
+                let base = match self.get_rush_stat().0 {
+                    NumStat::Val(num) => num,
+                    NumStat::Sg | NumStat::Lg => QB_SNEAK.max_gain,
+                };
+                mechanic!(self.utils, "Base yardage gain: {}", base);
+                base + self.calculate_run_yardage_modifier(&actual)
+            }
+            RunDirection::Break => QB_SNEAK.max_gain,
+        };
+        self.data.yardage = yardage.clamp(QB_SNEAK.max_loss, QB_SNEAK.max_gain);
+        self.data.ob = false;
+        self.finalize_yardage()
+    }
+
+    /// The runner's rushing result for a fresh (modified) run number, and whether that
+    /// run number is out of bounds.
+    fn get_rush_stat(&mut self) -> (NumStat, bool) {
+        let runner = RunUtils::get_runner(&self.play);
+
+        let run_num_modifier = self.get_run_modifier();
+        let run_num_full = self.utils.get_full_run_num();
+        let run_num = min(run_num_full.num + run_num_modifier, FacCard::get_max_rn());
+
+        (runner.rush_stat(run_num), run_num_full.ob)
+    }
+
     fn get_run_direction(&mut self) -> RunDirection {
         let card = &self.utils.get_fac();
         // let res = RunPlayData::get_fac_result(&play.offense_call.play_type, card);
@@ -158,20 +255,20 @@ impl<'a> RunContext<'a> {
     }
 
     fn handle_actual_run(&mut self, actual: &RunDirectionActual) -> PlayResult {
-        let rb = RunUtils::get_rb_stats(&self.play);
-
-        let run_num_modifier = self.get_run_modifier();
-        let run_num_full = self.utils.get_full_run_num();
-        let run_num = min(run_num_full.num + run_num_modifier, FacCard::get_max_rn());
-
-        let stat = RunUtils::get_rush_stat(&rb, run_num);
+        // Short and long gains happen past the line, so the blocking matchup doesn't apply.
+        let (stat, ob) = self.get_rush_stat();
         match stat {
-            stats::NumStat::Sg | stats::NumStat::Lg => {
+            stats::NumStat::Sg => {
                 (self.data.yardage, self.data.ob) = RunUtils::calculate_sg_yardage(&mut self.utils);
+                return self.finalize_yardage();
+            }
+            stats::NumStat::Lg => {
+                detail!(self.utils, "He breaks into the open");
+                return self.handle_long_gain();
             }
             stats::NumStat::Val(num) => {
-                self.data.yardage = *num;
-                self.data.ob = run_num_full.ob;
+                self.data.yardage = num;
+                self.data.ob = ob;
             }
         }
         mechanic!(self.utils, "Base yardage gain: {}", self.data.yardage);
@@ -231,7 +328,6 @@ impl<'a> RunContext<'a> {
         }
 
         let b = self.get_block_value(&result.offensive_boxes[0]);
-
         let t_opt = self.get_tackle_value(&result.defensive_boxes[0]);
         if t_opt.is_none() {
             detailf!(
@@ -268,8 +364,18 @@ impl<'a> RunContext<'a> {
         }
     }
 
-    fn get_block_value(&mut self, o_box: &OffensiveBox) -> i32 {
-        let b = PlayerUtils::get_blocks(self.play.offense.get_player_in_pos(o_box));
+    /// The block value of `blocker`. `BK` is the named blocking back, or the back in B1 when
+    /// the offense didn't name one.
+    fn get_block_value(&mut self, blocker: &RunBlocker) -> i32 {
+        let o_box = match blocker {
+            RunBlocker::Box(o_box) => *o_box,
+            RunBlocker::BlockingBack => self
+                .play
+                .offense_call
+                .blocking_back
+                .unwrap_or(OffensiveBox::B1),
+        };
+        let b = PlayerUtils::get_blocks(self.play.offense.get_player_in_pos(&o_box));
         mechanic2!(self.utils, "Box {:?} blocks for {}", o_box, b);
         b
     }
@@ -311,12 +417,11 @@ impl<'a> RunContext<'a> {
             // .flatten()
             .fold(0, |acc, ele| acc + PlayerUtils::get_tackles(ele));
 
-        let blocks = result
+        let blocks: i32 = result
             .offensive_boxes
             .iter()
-            .map(|s| self.play.offense.get_player_in_pos(s))
-            // .flatten()
-            .fold(0, |acc, ele| acc + PlayerUtils::get_blocks(ele));
+            .map(|s| self.get_block_value(s))
+            .sum();
 
         let modifier = match tackles.cmp(&blocks) {
             std::cmp::Ordering::Less => {
@@ -354,7 +459,8 @@ impl<'a> RunContext<'a> {
     }
 
     fn get_drawplay_impact(&mut self) -> i32 {
-        if self.play.offense_call.strategy == OffensiveStrategy::Draw
+        if self.state.rules.is_advanced()
+            && self.play.offense_call.strategy == OffensiveStrategy::Draw
             && (self.play.offense_call.play_type == OffensivePlayType::IL
                 || self.play.offense_call.play_type == OffensivePlayType::IR)
         {
@@ -386,6 +492,11 @@ impl<'a> RunContext<'a> {
 
     fn finalize_yardage(&mut self) -> PlayResult {
         let result = max(self.data.yardage, self.data.md.max_loss);
+        let result = if self.play.offense_call.strategy == OffensiveStrategy::Sneak {
+            result
+        } else {
+            self.utils.get_big_play_yards(self.play.big_play, result)
+        };
 
         let mut time = TIMES.run_play;
 
@@ -395,6 +506,10 @@ impl<'a> RunContext<'a> {
         }
 
         detail!(self.utils, format!("Gain of {} yards", result));
+        self.utils.record_stat(PlayStat::Rush {
+            player: RunUtils::get_runner(&self.play).id,
+            yards: result,
+        });
         return self.create_result(result, ResultType::Regular, time);
     }
 
@@ -410,7 +525,7 @@ impl<'a> RunContext<'a> {
     }
 }
 
-fn get_lg_yardage(c: char) -> i32 {
+pub fn get_lg_yardage(c: char) -> i32 {
     // match c {
     //     'A' => 100,
     //     'B' => 95,
@@ -437,3 +552,164 @@ fn get_lg_yardage(c: char) -> i32 {
     // let ascii_value = c as i32;
     100 - (c as i32 - 65) * 5
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        players::Roster,
+        standard_play::StandardOffenseCall,
+        testutils::{
+            card, deck, defense_call, offense_call, roster, roster_with, run, run_card, said,
+            standard_play, state,
+        },
+        RulesLevel,
+    };
+
+    /// A QB sneak into the middle, the FAC's IL result set to `il`.
+    fn sneak(offense: &Roster, state: &GameState, il: RunDirectionActual) -> PlayResult {
+        let play = standard_play(
+            offense,
+            &roster("Away"),
+            StandardOffenseCall {
+                strategy: OffensiveStrategy::Sneak,
+                ..offense_call(OffensivePlayType::IL, OffensiveBox::QB)
+            },
+            defense_call(DefensivePlay::PassDefense),
+        );
+        let il = FacData {
+            il: RunDirection::Actual(il),
+            ..card(1)
+        };
+        run(state, vec![il], play).unwrap().result
+    }
+
+    #[test]
+    fn test_sneak_needs_short_yardage() {
+        let play = standard_play(
+            &roster("Home"),
+            &roster("Away"),
+            StandardOffenseCall {
+                strategy: OffensiveStrategy::Sneak,
+                ..offense_call(OffensivePlayType::IL, OffensiveBox::QB)
+            },
+            defense_call(DefensivePlay::PassDefense),
+        );
+        let err = run(&state(), vec![card(1)], play).unwrap_err();
+        assert_eq!(err.code(), "invalid_call");
+    }
+
+    #[test]
+    fn test_sneak_is_held_to_a_short_push() {
+        let short = GameState {
+            first_down_target: 51,
+            ..state()
+        };
+
+        // 4 off the QB's column and 2 for the center's block, but a sneak tops out at 3.
+        let up_the_middle = RunDirectionActual {
+            offensive_boxes: vec![RunBlocker::Box(OffensiveBox::C)],
+            defensive_boxes: vec![],
+        };
+        let result = sneak(&roster("Home"), &short, up_the_middle);
+        assert_eq!(result.result, QB_SNEAK.max_gain);
+        assert!(matches!(result.stat, Some(PlayStat::Rush { yards: 3, .. })));
+
+        // Stuffed for -4, he only loses a yard.
+        let stuffed = roster_with("Home", |p| {
+            if let Player::QB(qb) = p {
+                qb.rushing = TwelveStats {
+                    stats: vec![NumStat::Val(-3); 12],
+                };
+            }
+        });
+        let linebacker = RunDirectionActual {
+            offensive_boxes: vec![],
+            defensive_boxes: vec![DefensiveBox::BoxF],
+        };
+        let result = sneak(&stuffed, &short, linebacker);
+        assert_eq!(result.result, QB_SNEAK.max_loss);
+    }
+
+    #[test]
+    fn test_designed_qb_run_uses_his_rushing_column() {
+        let play = standard_play(
+            &roster("Home"),
+            &roster("Away"),
+            offense_call(OffensivePlayType::IL, OffensiveBox::QB),
+            defense_call(DefensivePlay::PassDefense),
+        );
+        let il = FacData {
+            il: RunDirection::Actual(RunDirectionActual {
+                offensive_boxes: vec![],
+                defensive_boxes: vec![DefensiveBox::BoxF],
+            }),
+            ..card(1)
+        };
+        let result = run(&state(), vec![il], play).unwrap().result;
+
+        assert!(said(&result.details, "QB-1 keeps it"));
+        assert_eq!(result.result, 3);
+        assert!(
+            matches!(&result.stat, Some(PlayStat::Rush { player, yards: 3 }) if player == "QB-1")
+        );
+    }
+
+    #[test]
+    fn test_bk_is_the_named_blocking_back_or_else_b1() {
+        let offense = roster_with("Home", |p| {
+            if let Player::RB(rb) = p {
+                rb.blocks = if rb.id == "RB-1" { 3 } else { 2 };
+            }
+        });
+        let bk = FacData {
+            il: RunDirection::Actual(RunDirectionActual {
+                offensive_boxes: vec![RunBlocker::BlockingBack],
+                defensive_boxes: vec![],
+            }),
+            ..card(1)
+        };
+        let handoff = |target, blocking_back| {
+            let play = standard_play(
+                &offense,
+                &roster("Away"),
+                StandardOffenseCall {
+                    blocking_back,
+                    ..offense_call(OffensivePlayType::IL, target)
+                },
+                defense_call(DefensivePlay::PassDefense),
+            );
+            run(&state(), vec![bk.clone()], play).unwrap().result
+        };
+
+        // No blocking back named: B1 leads the way, as on the printed cards.
+        assert_eq!(handoff(OffensiveBox::B2, None).result, 4 + 3);
+        assert_eq!(
+            handoff(OffensiveBox::B1, Some(OffensiveBox::B2)).result,
+            4 + 2
+        );
+    }
+
+    #[test]
+    fn test_basic_rules_have_no_draw_modifier() {
+        let play = standard_play(
+            &roster("Home"),
+            &roster("Away"),
+            StandardOffenseCall {
+                strategy: OffensiveStrategy::Draw,
+                ..offense_call(OffensivePlayType::IL, OffensiveBox::B1)
+            },
+            defense_call(DefensivePlay::PassDefense),
+        );
+        // The game refuses a draw call under the basic rules, so go around validation to
+        // check the engine ignores one anyway.
+        let ran = |rules| {
+            let state = GameState { rules, ..state() };
+            let mut cards = deck(vec![card(1), run_card(2, 6), card(3), card(4)]);
+            let mut streamer = CardStreamer::new(&mut cards);
+            play.run_play(&state, &mut streamer)
+        };
+        assert!(said(&ran(RulesLevel::Advanced).mechanic, "Draw modifier"));
+        assert!(!said(&ran(RulesLevel::Basic).mechanic, "Draw modifier"));
+    }
+}
@@ -1,11 +1,15 @@
-//! [`GameEnvironment`]: the shared, read-only, long-lived bundle of external data every
-//! game needs to run (the league and the FAC deck template).
+//! [`GameEnvironment`]: the shared, long-lived bundle of external data every game needs to
+//! run (the league and the FAC deck template). It is read-only apart from the teams' depth
+//! charts, which coaches edit between and during games.
 //!
 //! It is the single place that loads these resources from disk. It is created once in
 //! `main`, held in the server's application state, and **borrowed** (never consumed) when a
 //! game is created, so one environment can back many games. See
 //! `docs/design/game-management.md` for the layering and ownership model.
 
+use std::sync::RwLock;
+
+use spf_core::lineup::DepthChart;
 use spf_core::persist;
 use spf_core::players::{Roster, TeamID, TeamList};
 
@@ -14,12 +18,15 @@ use super::fac::FacManager;
 /// All external data a [`Game`](super::Game) depends on.
 ///
 /// Fields are private and split by sharing profile:
-/// - `league` is read-only during play and shared across all games (never cloned into a game).
+/// - `league` is shared across all games; each game gets a clone of its two rosters. Only
+///   depth charts change after loading ([`save_depth_chart`](Self::save_depth_chart)).
 /// - `fac_deck` is a *template*; each game receives its own clone via [`new_deck`](Self::new_deck)
 ///   because the deck is mutated (consumed) as a game runs.
+/// - `data_dir` is where depth charts are written back to; `None` keeps them in memory.
 pub struct GameEnvironment {
-    league: TeamList,
+    league: RwLock<TeamList>,
     fac_deck: FacManager,
+    data_dir: Option<String>,
 }
 
 impl GameEnvironment {
@@ -28,20 +35,35 @@ impl GameEnvironment {
     pub fn load(data_dir: &str, fac_path: &str) -> Result<Self, String> {
         let league = persist::load_league(data_dir)?;
         let fac_deck = FacManager::from_csv(fac_path).map_err(|e| e.to_string())?;
-        Ok(Self { league, fac_deck })
+        Ok(Self {
+            league: RwLock::new(league),
+            fac_deck,
+            data_dir: Some(data_dir.to_string()),
+        })
+    }
+
+    /// Every team in the league.
+    pub fn team_ids(&self) -> Vec<TeamID> {
+        self.league.read().unwrap().teams.keys().cloned().collect()
     }
 
-    /// Read-only access to the league, for endpoints that look players/teams up.
-    // Public API for HTTP handlers; no in-crate caller yet (team resolution happens inside
-    // `Game::create_game`), so allow dead_code until a league-reading endpoint lands.
-    #[allow(dead_code)]
-    pub fn league(&self) -> &TeamList {
-        &self.league
+    /// A copy of a team's roster, for a new game.
+    pub(crate) fn roster(&self, id: &TeamID) -> Option<Roster> {
+        self.league.read().unwrap().get_team(id).cloned()
     }
 
-    /// Resolve a roster by team id (read-only borrow into the shared league).
-    pub(crate) fn roster(&self, id: &TeamID) -> Option<&Roster> {
-        self.league.get_team(id)
+    /// Replace a team's depth chart, both for games created from now on and in its data file.
+    pub fn save_depth_chart(&self, id: &TeamID, chart: &DepthChart) -> Result<(), String> {
+        if let Some(dir) = &self.data_dir {
+            persist::save_depth_chart(dir, id, chart)?;
+        }
+        let mut league = self.league.write().unwrap();
+        let roster = league
+            .teams
+            .get_mut(id)
+            .ok_or_else(|| format!("Unknown team: {}", id.to_string()))?;
+        *roster.depth_chart_mut() = chart.clone();
+        Ok(())
     }
 
     /// A fresh, independent deck for a new game (clones the shared template).
@@ -53,6 +75,10 @@ impl GameEnvironment {
     /// bypassing disk loading.
     #[cfg(test)]
     pub(crate) fn from_parts(league: TeamList, fac_deck: FacManager) -> Self {
-        Self { league, fac_deck }
+        Self {
+            league: RwLock::new(league),
+            fac_deck,
+            data_dir: None,
+        }
     }
 }
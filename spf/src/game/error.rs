@@ -0,0 +1,80 @@
+//! The errors a [`Game`](super::Game) and its plays return when a command can't be applied.
+//! Each kind has a stable [`code`](GameError::code) for clients to match on; the message
+//! (`Display`) is for people and may change.
+
+use std::fmt;
+
+use super::{engine::PlayType, lineup::LineupError, seats::Seat};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameError {
+    /// No play type has been picked for the next play.
+    NoPlaySet,
+    /// A lineup or call that belongs to a different kind of play than the one being set up.
+    WrongPlayType(PlayType),
+    /// Something else has to be set first, e.g. the lineup before the call.
+    NotReady(String),
+    /// The lineup was refused.
+    Lineup(LineupError),
+    /// The call isn't allowed with this lineup or these rules.
+    InvalidCall(String),
+    /// That play type can't come next; carries the ones that can.
+    IllegalPlayType { allowed: Vec<PlayType> },
+    /// There is no such play to take back (`None`: no plays at all).
+    NoSuchPlay(Option<u32>),
+    /// The seat may not do that now.
+    NotAllowed(Seat),
+    /// The engine itself got stuck, e.g. it ran out of usable FACs.
+    Engine(String),
+}
+
+impl GameError {
+    /// A stable, machine-readable name for the kind of error. Lineup errors use the
+    /// [`LineupError`] code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            GameError::NoPlaySet => "no_play_set",
+            GameError::WrongPlayType(_) => "wrong_play_type",
+            GameError::NotReady(_) => "not_ready",
+            GameError::Lineup(e) => e.code(),
+            GameError::InvalidCall(_) => "invalid_call",
+            GameError::IllegalPlayType { .. } => "illegal_play_type",
+            GameError::NoSuchPlay(_) => "no_such_play",
+            GameError::NotAllowed(_) => "not_allowed",
+            GameError::Engine(_) => "engine_error",
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::NoPlaySet => write!(f, "No Play Set"),
+            GameError::WrongPlayType(play_type) => write!(f, "Not for a {:?} play", play_type),
+            GameError::NotReady(msg) | GameError::InvalidCall(msg) | GameError::Engine(msg) => {
+                write!(f, "{}", msg)
+            }
+            GameError::Lineup(e) => write!(f, "{}", e),
+            GameError::IllegalPlayType { allowed } => write!(f, "Valid plays are {:?}", allowed),
+            GameError::NoSuchPlay(None) => write!(f, "No plays to undo"),
+            GameError::NoSuchPlay(Some(counter)) => {
+                write!(f, "No play {} to rewind to", counter)
+            }
+            GameError::NotAllowed(seat) => write!(f, "The {:?} seat can't do that now", seat),
+        }
+    }
+}
+
+impl From<LineupError> for GameError {
+    fn from(e: LineupError) -> Self {
+        GameError::Lineup(e)
+    }
+}
+
+/// For callers that only report errors (the simulator, log replay), so `?` keeps working in
+/// their `Result<_, String>` functions.
+impl From<GameError> for String {
+    fn from(e: GameError) -> Self {
+        e.to_string()
+    }
+}
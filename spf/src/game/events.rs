@@ -4,29 +4,63 @@
 //! (currently the WebSocket handler). See `docs/design/ws-events-architecture.md`.
 
 use serde::Serialize;
+use strum_macros::{EnumVariantNames, IntoStaticStr};
 use utoipa::ToSchema;
 
 use crate::game::{
-    engine::{DefenseIDLineup, OffenseIDLineup, PlayType},
-    GameState, PlayAndState,
+    engine::{DefenseCall, DefenseIDLineup, OffenseCall, OffenseIDLineup, PlayType},
+    GameState, GameTeams, PlayAndState,
 };
 
+/// A [`GameEvent`] with its place in the game's event stream. `seq` starts at 1 and goes up
+/// by one per event, so a client can spot a gap and ask for what it missed (`?since=<seq>`).
+/// Serialized as the event's own object plus a `seq` field.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SequencedEvent {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub event: GameEvent,
+}
+
 /// An event describing something that happened to the game.
 ///
 /// Serialized form is a tagged object: `{ "event": "<Variant>", "data": { … } }`.
 /// The set of variants is expected to grow; adding one is an additive change plus a
 /// single `emit()` call at the point the event occurs (Stage 2+).
-#[derive(Debug, Clone, Serialize, ToSchema)]
+///
+/// The variant's name (`<&str>::from(&event)`) is the `event` tag, which is what webhook
+/// filters match on.
+#[derive(Debug, Clone, Serialize, ToSchema, IntoStaticStr, EnumVariantNames)]
 #[serde(tag = "event", content = "data")]
 pub enum GameEvent {
     /// A new game was created.
     GameStarted { state: GameState },
 
-    /// The offensive lineup was set for the upcoming play.
-    OffensiveLineupSet { lineup: OffenseIDLineup },
+    /// `team` set its offensive lineup for the upcoming play. `lineup` is `None` for
+    /// subscribers whose seat can't see it until the play is run.
+    OffensiveLineupSet {
+        team: GameTeams,
+        lineup: Option<OffenseIDLineup>,
+    },
 
-    /// The defensive lineup was set for the upcoming play.
-    DefensiveLineupSet { lineup: DefenseIDLineup },
+    /// `team` set its defensive lineup; see `OffensiveLineupSet`.
+    DefensiveLineupSet {
+        team: GameTeams,
+        lineup: Option<DefenseIDLineup>,
+    },
+
+    /// `team` called the upcoming offensive play. `call` is `None` for subscribers whose
+    /// seat can't see it; the `PlayRun` event then carries both calls.
+    OffenseCallSet {
+        team: GameTeams,
+        call: Option<OffenseCall>,
+    },
+
+    /// `team` called the upcoming defense; see `OffenseCallSet`.
+    DefenseCallSet {
+        team: GameTeams,
+        call: Option<DefenseCall>,
+    },
 
     /// The next play type was selected.
     NextPlayTypeSet { play_type: PlayType },
@@ -36,6 +70,17 @@ pub enum GameEvent {
     /// Boxed because `PlayAndState` is significantly larger than the other variants
     /// (avoids bloating the enum's size for every event).
     PlayRun { play: Box<PlayAndState> },
-    // Future variants go here (e.g. penalties, clock/quarter changes, calls-set once
-    // OffenseCall/DefenseCall are serializable).
+
+    /// One or more plays were taken back; `state` is the restored pre-play state and
+    /// `play_type` the play the coaches now call again.
+    PlayUndone {
+        state: GameState,
+        undone: u32,
+        play_type: PlayType,
+    },
+
+    /// The clock ran out in the final quarter; `state` is the final state. Follows the
+    /// `PlayRun` of the last play.
+    GameEnded { state: GameState },
+    // Future variants go here (e.g. penalties, clock/quarter changes).
 }
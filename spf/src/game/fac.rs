@@ -2,12 +2,14 @@ use csv::ReaderBuilder;
 use regex::Regex;
 use serde::{Deserialize, Deserializer};
 use serde_derive::Serialize;
+use spf_core::trace;
 use std::error::Error;
 use std::fs::File;
 use std::str::FromStr;
 
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, SeedableRng};
 
 use super::{
     lineup::{DefensiveBox, OffensiveBox},
@@ -43,9 +45,17 @@ impl<'de> Deserialize<'de> for RunNum {
     }
 }
 
+/// Who blocks on a run: the player in an offensive box, or the back the offense named as its
+/// blocking back (`BK` on the card).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum RunBlocker {
+    Box(OffensiveBox),
+    BlockingBack,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RunDirectionActual {
-    pub offensive_boxes: Vec<OffensiveBox>,
+    pub offensive_boxes: Vec<RunBlocker>,
     pub defensive_boxes: Vec<DefensiveBox>,
 }
 
@@ -75,7 +85,7 @@ impl<'de> Deserialize<'de> for RunDirection {
 
         // create an empty vector to store the matches
         let mut def_list: Vec<DefensiveBox> = Vec::new();
-        let mut off_list: Vec<OffensiveBox> = Vec::new();
+        let mut off_list: Vec<RunBlocker> = Vec::new();
 
         // iterate over the matches and push them to the vector
         for m in re.find_iter(instr.as_str()) {
@@ -84,7 +94,10 @@ impl<'de> Deserialize<'de> for RunDirection {
                 1 => def_list.push(
                     DefensiveBox::from_str(format!("{}", s.to_lowercase()).as_str()).unwrap(),
                 ),
-                2 => off_list.push(OffensiveBox::from_str(s.to_lowercase().as_str()).unwrap()),
+                2 if s == "BK" => off_list.push(RunBlocker::BlockingBack),
+                2 => off_list.push(RunBlocker::Box(
+                    OffensiveBox::from_str(s.to_lowercase().as_str()).unwrap(),
+                )),
                 _ => println!("Error {}", instr),
             };
         }
@@ -207,6 +220,13 @@ pub struct FacManager {
     /// the sole source of engine nondeterminism. `false` for decks injected via
     /// [`from_cards`](Self::from_cards) so tests get a reproducible, ordered draw sequence.
     shuffle_on_refill: bool,
+    /// Optional seeded shuffle source, so a shuffling deck can still replay the same draws
+    /// (used by batch simulation). `None` shuffles from the thread RNG.
+    #[serde(skip)]
+    rng: Option<StdRng>,
+    /// The seed `rng` was created from, recorded so a game's log can rebuild the same deck.
+    #[serde(skip)]
+    seed: Option<u64>,
 }
 
 impl FacManager {
@@ -224,6 +244,8 @@ impl FacManager {
             facs: cards,
             deck,
             shuffle_on_refill: false,
+            rng: None,
+            seed: None,
         }
     }
 
@@ -237,14 +259,33 @@ impl FacManager {
             facs,
             deck: vec![],
             shuffle_on_refill: true,
+            rng: None,
+            seed: None,
         })
     }
 
+    /// Shuffle from a seeded RNG instead of the thread RNG, so the same seed reproduces the
+    /// same draw sequence. Resets the draw deck so the next draw starts from a fresh shuffle.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Some(StdRng::seed_from_u64(seed));
+        self.seed = Some(seed);
+        self.deck.clear();
+        self
+    }
+
+    /// The seed passed to [`with_seed`](Self::with_seed), if any.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
     pub fn get_fac(&mut self, force_shuffle: bool) -> FacCard {
         if force_shuffle || self.deck.is_empty() {
             self.deck = self.facs.clone();
             if self.shuffle_on_refill {
-                self.deck.shuffle(&mut thread_rng());
+                match self.rng.as_mut() {
+                    Some(rng) => self.deck.shuffle(rng),
+                    None => self.deck.shuffle(&mut thread_rng()),
+                }
             } else {
                 // Preserve the caller's draw order across refills.
                 self.deck.reverse();
@@ -270,7 +311,7 @@ pub fn read_csv_file(filename: &str) -> Result<Vec<FacData>, Box<dyn Error>> {
         records.push(record);
     }
 
-    println!("Done");
+    trace!("Done");
 
     return Ok(records);
 }
@@ -309,6 +350,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bk_is_the_blocking_back_not_a_box() {
+        let actual = |s: &str| match serde_json::from_str(&format!("\"{}\"", s)).unwrap() {
+            RunDirection::Actual(a) => a,
+            RunDirection::Break => panic!("{} is not a break", s),
+        };
+        let bk = actual("BK vs G");
+        assert_eq!(bk.offensive_boxes, vec![RunBlocker::BlockingBack]);
+        assert_eq!(bk.defensive_boxes, vec![DefensiveBox::BoxG]);
+        assert_eq!(
+            actual("LG + LT").offensive_boxes,
+            vec![
+                RunBlocker::Box(OffensiveBox::LG),
+                RunBlocker::Box(OffensiveBox::LT)
+            ]
+        );
+    }
+
     #[test]
     fn test_from_cards_draws_in_order_without_shuffle() {
         let cards = vec![data_card(1), data_card(2), data_card(3)];
@@ -332,6 +391,29 @@ mod tests {
         assert_eq!(card_id(&mgr.get_fac(false)), 20);
     }
 
+    #[test]
+    fn test_seeded_shuffle_is_reproducible() {
+        let shuffling_deck = || FacManager {
+            facs: (1..=20).map(data_card).collect(),
+            deck: vec![],
+            shuffle_on_refill: true,
+            rng: None,
+            seed: None,
+        };
+        let mut a = shuffling_deck().with_seed(83);
+        let mut b = shuffling_deck().with_seed(83);
+
+        // Two full passes, so the refill reshuffle is covered too.
+        for draw in 0..40 {
+            assert_eq!(
+                card_id(&a.get_fac(false)),
+                card_id(&b.get_fac(false)),
+                "draw {} differs for the same seed",
+                draw
+            );
+        }
+    }
+
     #[test]
     fn test_empty_injected_deck_yields_z() {
         let mut mgr = FacManager::from_cards(vec![]);
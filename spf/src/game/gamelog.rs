@@ -0,0 +1,368 @@
+//! The append-only game log: every command that changed a [`Game`], in order, from creation
+//! (rosters, options and deck seed) through lineups, calls, plays and undos.
+//!
+//! Unlike [`GameEvent`](super::events::GameEvent), which is a transient broadcast of what
+//! happened, the log records what was *asked for*, so [`Game::replay`] can feed it back
+//! through the same methods and rebuild an identical game. On disk it is JSON lines, one
+//! entry per line, flushed as each entry is recorded so a crashed server loses at most the
+//! line it was writing.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+};
+
+use serde::{Deserialize, Serialize};
+use spf_core::persist::TeamData;
+
+use super::{
+    engine::{DefenseCall, DefenseIDLineup, OffenseCall, OffenseIDLineup, PlayType},
+    environment::GameEnvironment,
+    seats::Seat,
+    Game, GameOptions, GameState,
+};
+
+/// One recorded command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "entry", content = "data")]
+pub enum LogEntry {
+    /// Always the first entry. `seed` is `None` for a deck that wasn't seeded, which makes
+    /// the log an audit trail only.
+    Created {
+        home: TeamData,
+        away: TeamData,
+        seed: Option<u64>,
+        options: GameOptions,
+    },
+    OffensiveLineupSet {
+        lineup: OffenseIDLineup,
+    },
+    DefensiveLineupSet {
+        lineup: DefenseIDLineup,
+    },
+    OffenseCallSet {
+        call: OffenseCall,
+    },
+    DefenseCallSet {
+        call: DefenseCall,
+    },
+    NextPlayTypeSet {
+        play_type: PlayType,
+    },
+    /// A FAC drawn outside of a play (e.g. by the simulator to pick a call).
+    CardDrawn,
+    /// The current play was run; `new_state` is checked on replay to catch divergence.
+    PlayRun {
+        new_state: GameState,
+    },
+    PlayUndone {
+        to: Option<u32>,
+    },
+}
+
+/// A game's entries, optionally mirrored to a file as they are recorded.
+#[derive(Default)]
+pub struct GameLog {
+    entries: Vec<LogEntry>,
+    file: Option<File>,
+}
+
+impl GameLog {
+    pub(crate) fn record(&mut self, entry: LogEntry) {
+        if let Some(file) = self.file.as_mut() {
+            if let Err(e) = write_entry(file, &entry) {
+                // Keep the game going; the in-memory log is still complete.
+                eprintln!("Game log write failed, no longer persisting: {}", e);
+                self.file = None;
+            }
+        }
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    /// Start mirroring to `path`: writes the entries so far, then appends each new one.
+    fn persist_to(&mut self, path: &str) -> Result<(), String> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| format!("{}: {}", path, e))?;
+        for entry in &self.entries {
+            write_entry(&mut file, entry).map_err(|e| format!("{}: {}", path, e))?;
+        }
+        self.file = Some(file);
+        Ok(())
+    }
+}
+
+fn write_entry(file: &mut File, entry: &LogEntry) -> Result<(), String> {
+    let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+    file.flush().map_err(|e| e.to_string())
+}
+
+/// The path of the log called `name` in `log_dir`. Only a bare file name is accepted, so a
+/// request can't reach outside the log directory.
+pub fn log_path(log_dir: &str, name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(format!("Not a game log name: {}", name));
+    }
+    Ok(format!("{}/{}", log_dir, name))
+}
+
+/// Load a log written by [`Game::persist_log`].
+pub fn read_log(path: &str) -> Result<Vec<LogEntry>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    parse_log(&text)
+}
+
+fn parse_log(text: &str) -> Result<Vec<LogEntry>, String> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    let mut entries = vec![];
+    for (n, line) in lines.iter().enumerate() {
+        match serde_json::from_str(line) {
+            Ok(entry) => entries.push(entry),
+            // A torn final line is what a crash mid-write leaves behind; drop it.
+            Err(_) if n + 1 == lines.len() && !text.ends_with('\n') => break,
+            Err(e) => return Err(format!("Log line {}: {}", n + 1, e)),
+        }
+    }
+    Ok(entries)
+}
+
+impl Game {
+    /// Everything recorded for this game so far.
+    pub fn log(&self) -> &[LogEntry] {
+        self.log.entries()
+    }
+
+    /// The log as `seat` may read it. Lineups and calls recorded since the last play was
+    /// run (or undone) are left out for a side the seat can't see, and the deck seed, which
+    /// would let a coach deal the cards ahead, is withheld until the game is over.
+    pub fn log_for(&self, seat: Seat) -> Vec<LogEntry> {
+        let view = self.view(seat);
+        let offense = view.can_see(self.state.possession);
+        let defense = view.can_see(self.state.possession.other_team());
+        let entries = self.log.entries();
+        let pending = entries
+            .iter()
+            .rposition(|e| matches!(e, LogEntry::PlayRun { .. } | LogEntry::PlayUndone { .. }))
+            .map_or(0, |i| i + 1);
+        let over = self.state.is_game_over();
+
+        entries
+            .iter()
+            .enumerate()
+            .filter(|(i, entry)| {
+                *i < pending
+                    || match entry {
+                        LogEntry::OffensiveLineupSet { .. } | LogEntry::OffenseCallSet { .. } => {
+                            offense
+                        }
+                        LogEntry::DefensiveLineupSet { .. } | LogEntry::DefenseCallSet { .. } => {
+                            defense
+                        }
+                        _ => true,
+                    }
+            })
+            .map(|(_, entry)| match entry {
+                LogEntry::Created {
+                    home,
+                    away,
+                    options,
+                    ..
+                } if !over => LogEntry::Created {
+                    home: home.clone(),
+                    away: away.clone(),
+                    seed: None,
+                    options: options.clone(),
+                },
+                other => other.clone(),
+            })
+            .collect()
+    }
+
+    /// Mirror this game's log to `path` (JSON lines), including what's already recorded.
+    pub fn persist_log(&mut self, path: &str) -> Result<(), String> {
+        self.log.persist_to(path)
+    }
+
+    /// Rebuild a game by re-applying a recorded log. The FAC deck is the environment's,
+    /// reshuffled from the recorded seed; rosters come from the log itself. Fails if the
+    /// log doesn't start with `Created`, the deck wasn't seeded, or a replayed play lands
+    /// somewhere other than where the log says it did.
+    pub fn replay(env: &GameEnvironment, entries: &[LogEntry]) -> Result<Game, String> {
+        let (first, rest) = entries.split_first().ok_or("Empty game log")?;
+        let mut game = match first {
+            LogEntry::Created {
+                home,
+                away,
+                seed,
+                options,
+            } => {
+                let seed = seed.ok_or("The game's deck wasn't seeded, so it can't be replayed")?;
+                Game::build(
+                    home.clone().into_roster(),
+                    away.clone().into_roster(),
+                    env.new_deck().with_seed(seed),
+                    options.clone(),
+                )
+            }
+            _ => return Err("Game log must start with Created".to_string()),
+        };
+
+        for (n, entry) in rest.iter().enumerate() {
+            game.apply(entry)
+                .map_err(|e| format!("Log entry {}: {}", n + 1, e))?;
+        }
+        Ok(game)
+    }
+
+    fn apply(&mut self, entry: &LogEntry) -> Result<(), String> {
+        match entry {
+            LogEntry::Created { .. } => return Err("Game already created".to_string()),
+            LogEntry::OffensiveLineupSet { lineup } => {
+                self.set_offensive_lineup_from_ids(lineup)?
+            }
+            LogEntry::DefensiveLineupSet { lineup } => {
+                self.set_defensive_lineup_from_ids(lineup)?
+            }
+            LogEntry::OffenseCallSet { call } => self.set_offense_call(call.clone())?,
+            LogEntry::DefenseCallSet { call } => self.set_defense_call(call.clone())?,
+            LogEntry::NextPlayTypeSet { play_type } => self.set_next_play_type(*play_type)?,
+            LogEntry::CardDrawn => {
+                self.draw_card()?;
+            }
+            LogEntry::PlayRun { new_state } => {
+                let res = self.run_current_play()?;
+                if res.new_state != *new_state {
+                    return Err(format!(
+                        "Replay diverged at play {}",
+                        new_state.play_counter
+                    ));
+                }
+            }
+            LogEntry::PlayUndone { to } => {
+                self.undo(*to)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{fac::FacManager, testutils, GameOptions, GameTeams};
+
+    #[test]
+    fn test_log_path_only_takes_names_in_the_log_dir() {
+        assert_eq!(
+            log_path("games", " Detroit-at-Chicago-7.jsonl\n"),
+            Ok("games/Detroit-at-Chicago-7.jsonl".to_string())
+        );
+        for name in [
+            "",
+            "/etc/passwd",
+            "../secrets.jsonl",
+            "sub/x.jsonl",
+            "a\\b",
+            "..",
+        ] {
+            assert!(log_path("games", name).is_err(), "{:?} accepted", name);
+        }
+    }
+
+    #[test]
+    fn test_parse_log_drops_torn_final_line() {
+        let text = "{\"entry\":\"CardDrawn\"}\n{\"entry\":\"PlayUndone\",\"data\":{\"to\":3}}\n{\"entry\":\"Pla";
+        let entries = parse_log(text).expect("torn tail is tolerated");
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(entries[1], LogEntry::PlayUndone { to: Some(3) }));
+    }
+
+    #[test]
+    fn test_parse_log_rejects_corrupt_middle_line() {
+        let text = "{\"entry\":\"CardDrawn\"}\nnot json\n{\"entry\":\"CardDrawn\"}\n";
+        assert!(parse_log(text).is_err());
+    }
+
+    #[test]
+    fn test_replay_needs_created_first() {
+        let env = GameEnvironment::from_parts(
+            spf_core::players::TeamList::from_rosters(vec![]),
+            FacManager::from_cards(vec![]),
+        );
+        assert!(Game::replay(&env, &[]).is_err());
+        assert!(Game::replay(&env, &[LogEntry::CardDrawn]).is_err());
+    }
+
+    #[test]
+    fn test_replay_rebuilds_a_game_with_an_undo() {
+        let mut game = testutils::seeded_game(testutils::drive_cards(), 7, GameOptions::default());
+        testutils::kick_off_to_home(&mut game);
+        for _ in 0..3 {
+            testutils::run_standard(&mut game, testutils::handoff());
+        }
+        game.undo(None).unwrap();
+        testutils::run_standard(&mut game, testutils::handoff());
+
+        // The log, undo included, rebuilds the same game after a trip through disk.
+        let path = std::env::temp_dir().join(format!("spf-replay-{}.jsonl", std::process::id()));
+        let path = path.to_str().unwrap();
+        game.persist_log(path).expect("log writes");
+        let entries = read_log(path).expect("log reads back");
+        let _ = std::fs::remove_file(path);
+
+        let env = GameEnvironment::from_parts(
+            spf_core::players::TeamList::from_rosters(vec![]),
+            testutils::deck(testutils::drive_cards()),
+        );
+        let rebuilt = Game::replay(&env, &entries).expect("log replays");
+        assert_eq!(rebuilt.state, game.state);
+        assert_eq!(rebuilt.past_plays.len(), game.past_plays.len());
+        assert_eq!(rebuilt.log().len(), game.log().len());
+    }
+
+    #[test]
+    fn test_replay_matches_a_solitaire_game_after_a_failed_run() {
+        let options = GameOptions {
+            solitaire: Some(GameTeams::Away),
+            ..GameOptions::default()
+        };
+        let mut game = testutils::seeded_game(testutils::solitaire_cards(), 7, options);
+        testutils::kick_off_to_home(&mut game);
+        let drawn = |game: &Game| {
+            game.log()
+                .iter()
+                .filter(|e| matches!(e, LogEntry::CardDrawn))
+                .count()
+        };
+
+        // Home hasn't called yet, so the engine doesn't draw for Away.
+        let err = game.run_current_play().unwrap_err();
+        assert_eq!(err.code(), "not_ready");
+        assert_eq!(drawn(&game), 0);
+
+        game.set_offense_call(testutils::handoff()).unwrap();
+        game.run_current_play().unwrap();
+        assert_eq!(drawn(&game), 1);
+        assert!(game
+            .log()
+            .iter()
+            .any(|e| matches!(e, LogEntry::DefenseCallSet { .. })));
+
+        let env = GameEnvironment::from_parts(
+            spf_core::players::TeamList::from_rosters(vec![]),
+            testutils::deck(testutils::solitaire_cards()),
+        );
+        let rebuilt = Game::replay(&env, game.log()).expect("log replays");
+        assert_eq!(rebuilt.state, game.state);
+        assert_eq!(rebuilt.log().len(), game.log().len());
+    }
+}
@@ -2,18 +2,22 @@ use serde_derive::Serialize;
 
 use super::{
     engine::{
-        kickplay::KickPlayImpl, CardStreamer, DefenseCall, DefenseIDLineup, OffenseCall,
-        OffenseIDLineup, PlayImpl, PlayResult, PlayType,
+        defs::KICKOFF, kickplay::KickPlayImpl, CardStreamer, DefenseCall, DefenseIDLineup,
+        KickoffKind, OffenseCall, OffenseIDLineup, PlayImpl, PlayResult, PlayType,
     },
+    error::GameError,
+    lineup::LineupError,
     players::{KRStats, KStats, Player, Roster},
     GameState, Play,
 };
 
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct KickoffPlay {
-    pub onside: Option<bool>,
+    pub kind: Option<KickoffKind>,
+    pub onside_expected: bool,
     pub kr: Option<KRStats>,
     pub k: Option<KStats>,
+    pub coverage: Vec<Player>,
 }
 
 impl KickoffPlay {
@@ -25,22 +29,29 @@ impl KickoffPlay {
 }
 
 impl PlayImpl for KickoffPlay {
-    fn validate(&self) -> Result<(), String> {
-        let _ = self.onside.as_ref().ok_or("Offense not set");
-        let _ = self.kr.as_ref().ok_or("Defense Lineup not set");
-        let _ = self.k.as_ref().ok_or("Offense Lineup not set");
+    fn validate(&self, _game_state: &GameState) -> Result<(), GameError> {
+        if self.k.is_none() {
+            return Err(GameError::NotReady("Kicking lineup not set".to_string()));
+        }
+        if self.kr.is_none() {
+            return Err(GameError::NotReady("Receiving lineup not set".to_string()));
+        }
         Ok(())
     }
 
-    fn set_offense_call(&mut self, call: OffenseCall) -> Result<(), String> {
+    fn set_offense_call(&mut self, call: OffenseCall) -> Result<(), GameError> {
         let c = call
             .as_kickoff_offense_call()
-            .ok_or("Not a call for a Kickoff".to_string())?;
-        self.onside = Some(c.onside);
+            .ok_or(GameError::WrongPlayType(PlayType::Kickoff))?;
+        self.kind = Some(c.kind);
         Ok(())
     }
 
-    fn set_defense_call(&mut self, _call: DefenseCall) -> Result<(), String> {
+    fn set_defense_call(&mut self, call: DefenseCall) -> Result<(), GameError> {
+        let c = call
+            .as_kickoff_defense_call()
+            .ok_or(GameError::WrongPlayType(PlayType::Kickoff))?;
+        self.onside_expected = c.onside_expected;
         Ok(())
     }
 
@@ -48,22 +59,58 @@ impl PlayImpl for KickoffPlay {
         &mut self,
         lineup: &OffenseIDLineup,
         roster: &Roster,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         let l = lineup
             .as_kickoff_id_offense_lineup()
-            .ok_or("Bad Lineup for Kickoff".to_string())?;
+            .ok_or(GameError::WrongPlayType(PlayType::Kickoff))?;
 
         self.k = Player::is_k(
             roster
                 .get_player(&l.k)
-                .ok_or(format!("Unknown player: {}", l.k))?
+                .ok_or_else(|| LineupError::UnknownPlayer {
+                    position: "K".to_string(),
+                    id: l.k.clone(),
+                })?
                 .get_full_player(),
         );
 
         if self.k.is_none() {
-            return Err("Player is not a K".to_string());
+            return Err(LineupError::WrongPosition {
+                position: "K".to_string(),
+                id: l.k.clone(),
+            }
+            .into());
         }
 
+        if l.coverage.len() > KICKOFF.max_coverage {
+            return Err(LineupError::IllegalFormation(format!(
+                "At most {} players can cover a kick",
+                KICKOFF.max_coverage
+            ))
+            .into());
+        }
+
+        self.coverage = l
+            .coverage
+            .iter()
+            .map(|id| {
+                let player = roster
+                    .get_player(id)
+                    .ok_or_else(|| LineupError::UnknownPlayer {
+                        position: "coverage".to_string(),
+                        id: id.clone(),
+                    })?
+                    .get_full_player();
+                match player {
+                    Player::LB(_) | Player::DL(_) => Ok(player),
+                    _ => Err(LineupError::WrongPosition {
+                        position: "coverage".to_string(),
+                        id: id.clone(),
+                    }),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
         return Ok(());
     }
 
@@ -71,20 +118,27 @@ impl PlayImpl for KickoffPlay {
         &mut self,
         lineup: &DefenseIDLineup,
         roster: &Roster,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         let l = lineup
             .as_kickoff_id_defense_lineup()
-            .ok_or("Bad type".to_string())?;
+            .ok_or(GameError::WrongPlayType(PlayType::Kickoff))?;
 
         self.kr = Player::is_kr(
             roster
                 .get_player(&l.kr)
-                .ok_or(format!("Unknown player: {}", l.kr))?
+                .ok_or_else(|| LineupError::UnknownPlayer {
+                    position: "KR".to_string(),
+                    id: l.kr.clone(),
+                })?
                 .get_full_player(),
         );
 
         if self.kr.is_none() {
-            return Err("Player is not a KR".to_string());
+            return Err(LineupError::WrongPosition {
+                position: "KR".to_string(),
+                id: l.kr.clone(),
+            }
+            .into());
         }
 
         return Ok(());
@@ -106,3 +160,53 @@ impl PlayImpl for KickoffPlay {
         PlayType::Kickoff
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        lineup::KickoffIDOffenseLineup,
+        testutils::{card, kickoff_lineups, roster, run, state},
+    };
+
+    fn covered_by(coverage: &[&str]) -> Result<(), GameError> {
+        let lineup = OffenseIDLineup::KickoffIDOffenseLineup(KickoffIDOffenseLineup {
+            k: "K-1".into(),
+            coverage: coverage.iter().map(|id| id.to_string()).collect(),
+        });
+        KickoffPlay::new().set_offense_lineup(&lineup, &roster("Home"))
+    }
+
+    #[test]
+    fn test_linemen_and_linebackers_cover_kicks() {
+        assert!(covered_by(&["DL-1", "LB-1"]).is_ok());
+
+        let err = covered_by(&["WR-1"]).unwrap_err();
+        assert_eq!(err.code(), "wrong_position");
+        let err = covered_by(&["LB-9"]).unwrap_err();
+        assert_eq!(err.code(), "unknown_player");
+    }
+
+    #[test]
+    fn test_only_a_handful_cover_a_kick() {
+        let too_many = vec!["DL-1"; KICKOFF.max_coverage + 1];
+        let err = covered_by(&too_many).unwrap_err();
+        assert_eq!(err.code(), "illegal_formation");
+        assert!(covered_by(&too_many[1..]).is_ok());
+    }
+
+    #[test]
+    fn test_kickoff_needs_both_lineups_to_run() {
+        let (kicker, returner) = kickoff_lineups(0);
+        let mut play = KickoffPlay::new();
+        let err = run(&state(), vec![card(1)], Box::new(play.clone())).unwrap_err();
+        assert_eq!(err.code(), "not_ready");
+
+        play.set_offense_lineup(&kicker, &roster("Home")).unwrap();
+        let err = run(&state(), vec![card(1)], Box::new(play.clone())).unwrap_err();
+        assert_eq!(err.code(), "not_ready");
+
+        play.set_defense_lineup(&returner, &roster("Away")).unwrap();
+        assert!(run(&state(), vec![card(1)], Box::new(play)).is_ok());
+    }
+}
@@ -0,0 +1,180 @@
+//! Dry-run lineup checks. Setting a lineup stops at the first problem; these report every
+//! problem at once (each box's player, the formation, and for the defense the row sizes its
+//! strategy allows), so a UI can show them all while the coach builds the lineup. Nothing
+//! about the game changes.
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::{
+    engine::{defs::DEFENSE_STRATEGY_LIMITS, DefenseIDLineup, OffenseIDLineup, PlayType},
+    error::GameError,
+    lineup::{DefensiveRow, LineupError},
+    standard_play::DefensiveStrategy,
+    Game,
+};
+
+/// One thing wrong with a lineup.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LineupProblem {
+    /// The same codes as the error body of setting the lineup, e.g. `wrong_position`.
+    pub code: String,
+    pub message: String,
+    /// The box it is about (`"QB"`, `"box_f"`, …); absent for formation problems.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+}
+
+impl From<GameError> for LineupProblem {
+    fn from(e: GameError) -> Self {
+        let position = match &e {
+            GameError::Lineup(l) => l.position().map(String::from),
+            _ => None,
+        };
+        Self {
+            code: e.code().to_string(),
+            message: e.to_string(),
+            position,
+        }
+    }
+}
+
+/// Row 2 (linebacker) and row 3 (secondary) sizes a defensive strategy allows.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FormationLimit {
+    pub strategy: DefensiveStrategy,
+    pub row2: i32,
+    pub row3: i32,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LineupReport {
+    /// Whether setting this lineup would succeed.
+    pub legal: bool,
+    pub problems: Vec<LineupProblem>,
+    /// Defense only: the formations the strategy allows, or those of every strategy that
+    /// limits them when none is known. `Straight` allows any legal lineup.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub formations: Vec<FormationLimit>,
+}
+
+impl LineupReport {
+    fn new(problems: Vec<GameError>, formations: Vec<FormationLimit>) -> Self {
+        Self {
+            legal: problems.is_empty(),
+            problems: problems.into_iter().map(LineupProblem::from).collect(),
+            formations,
+        }
+    }
+}
+
+/// The strategies with row limits, in the order they are listed.
+const LIMITED_STRATEGIES: [DefensiveStrategy; 3] = [
+    DefensiveStrategy::DoubleCover,
+    DefensiveStrategy::TripleCover,
+    DefensiveStrategy::DoubleCoverX2,
+];
+
+fn formation_limits(strategy: &DefensiveStrategy) -> Vec<FormationLimit> {
+    DEFENSE_STRATEGY_LIMITS
+        .get(strategy)
+        .into_iter()
+        .flatten()
+        .map(|vals| FormationLimit {
+            strategy: strategy.clone(),
+            row2: vals.row2,
+            row3: vals.row3,
+        })
+        .collect()
+}
+
+impl Game {
+    /// Everything wrong with `lineup` as the offense for the next play.
+    pub fn check_offensive_lineup(
+        &self,
+        lineup: &OffenseIDLineup,
+    ) -> Result<LineupReport, GameError> {
+        let play_type = self
+            .next_play
+            .as_ref()
+            .ok_or(GameError::NoPlaySet)?
+            .get_type();
+        let roster = self.get_current_off_roster();
+        let problems = match (lineup, play_type) {
+            (OffenseIDLineup::StandardIDOffenseLineup(l), PlayType::Standard) => l
+                .problems(roster)
+                .into_iter()
+                .map(GameError::from)
+                .collect(),
+            // Other lineups have a single box, so the first problem is the only one. A
+            // fresh play keeps the pending one untouched.
+            _ => play_type
+                .create_impl()
+                .set_offense_lineup(lineup, roster)
+                .err()
+                .into_iter()
+                .collect(),
+        };
+        Ok(LineupReport::new(problems, vec![]))
+    }
+
+    /// Everything wrong with `lineup` as the defense for the next play. Row sizes are also
+    /// checked against `strategy`, or the pending defense call's if none is given.
+    pub fn check_defensive_lineup(
+        &self,
+        lineup: &DefenseIDLineup,
+        strategy: Option<DefensiveStrategy>,
+    ) -> Result<LineupReport, GameError> {
+        let play_type = self
+            .next_play
+            .as_ref()
+            .ok_or(GameError::NoPlaySet)?
+            .get_type();
+        let roster = self.get_current_def_roster();
+        let l = match (lineup, play_type) {
+            (DefenseIDLineup::StandardIDDefenseLineup(l), PlayType::Standard) => l,
+            _ => {
+                let problems = play_type
+                    .create_impl()
+                    .set_defense_lineup(lineup, roster)
+                    .err()
+                    .into_iter()
+                    .collect();
+                return Ok(LineupReport::new(problems, vec![]));
+            }
+        };
+
+        let mut problems: Vec<GameError> = l
+            .problems(roster)
+            .into_iter()
+            .map(GameError::from)
+            .collect();
+
+        let strategy = strategy.or_else(|| {
+            self.defcall
+                .as_ref()
+                .and_then(|c| c.as_standard_defense_call())
+                .map(|c| c.strategy.clone())
+        });
+        let formations = match &strategy {
+            Some(s) => formation_limits(s),
+            None => LIMITED_STRATEGIES
+                .iter()
+                .flat_map(formation_limits)
+                .collect(),
+        };
+        if let Some(s) = &strategy {
+            let row2 = l.count_in_row(DefensiveRow::Row2);
+            let row3 = l.count_in_row(DefensiveRow::Row3);
+            let fits = formations.is_empty()
+                || formations.iter().any(|f| f.row2 == row2 && f.row3 == row3);
+            if !fits {
+                problems.push(GameError::Lineup(LineupError::IllegalFormation(format!(
+                    "Row2: {} Row3: {} - illegal lineup for {:?}",
+                    row2, row3, s
+                ))));
+            }
+        }
+        Ok(LineupReport::new(problems, formations))
+    }
+}
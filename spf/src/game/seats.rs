@@ -0,0 +1,211 @@
+//! Seats: who is at the table. Each game issues one token per seat (home coach, away coach,
+//! spectator) when it is created. The HTTP layer maps a request's token to a [`Seat`], asks
+//! the game whether that seat may do something ([`Game::authorize`]) and what it may see
+//! ([`Game::view`]). A side's lineup and call stay hidden from the other coach until the
+//! play is run; spectators see them only if the game doesn't hide calls.
+
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{
+    error::GameError,
+    events::{GameEvent, SequencedEvent},
+    Game, GameTeams,
+};
+
+const TOKEN_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
+pub enum Seat {
+    Home,
+    Away,
+    Spectator,
+}
+
+impl Seat {
+    /// The team this seat coaches; `None` for a spectator.
+    pub fn team(&self) -> Option<GameTeams> {
+        match self {
+            Seat::Home => Some(GameTeams::Home),
+            Seat::Away => Some(GameTeams::Away),
+            Seat::Spectator => None,
+        }
+    }
+}
+
+/// The secret handed to each seat at game creation.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SeatTokens {
+    pub home: String,
+    pub away: String,
+    pub spectator: String,
+}
+
+impl SeatTokens {
+    pub(crate) fn generate() -> Self {
+        let token = || {
+            rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(TOKEN_LEN)
+                .map(char::from)
+                .collect()
+        };
+        Self {
+            home: token(),
+            away: token(),
+            spectator: token(),
+        }
+    }
+
+    fn seat(&self, token: &str) -> Option<Seat> {
+        [
+            (&self.home, Seat::Home),
+            (&self.away, Seat::Away),
+            (&self.spectator, Seat::Spectator),
+        ]
+        .into_iter()
+        .find(|(t, _)| t.as_str() == token)
+        .map(|(_, seat)| seat)
+    }
+}
+
+/// What a seat wants to do to the game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeatAction {
+    /// Set the offense's lineup or call, or pick the next play type.
+    Offense,
+    /// Set the defense's lineup or call.
+    Defense,
+    /// Anything either coach may do (run the play, undo, save).
+    Coach,
+}
+
+/// What one seat is allowed to see of the play being set up.
+#[derive(Debug, Clone, Copy)]
+pub struct SeatView {
+    seat: Seat,
+    spectators_see_calls: bool,
+}
+
+impl SeatView {
+    /// Whether `side`'s pending lineup and call are visible to this seat.
+    pub fn can_see(&self, side: GameTeams) -> bool {
+        match self.seat.team() {
+            Some(team) => team == side,
+            None => self.spectators_see_calls,
+        }
+    }
+
+    /// The event as this seat may receive it: pending lineups and calls of a side it can't
+    /// see are blanked. Everything else, including `PlayRun`, passes through unchanged.
+    pub fn redact(&self, event: SequencedEvent) -> SequencedEvent {
+        SequencedEvent {
+            seq: event.seq,
+            event: self.redact_event(event.event),
+        }
+    }
+
+    fn redact_event(&self, event: GameEvent) -> GameEvent {
+        match event {
+            GameEvent::OffensiveLineupSet { team, .. } if !self.can_see(team) => {
+                GameEvent::OffensiveLineupSet { team, lineup: None }
+            }
+            GameEvent::DefensiveLineupSet { team, .. } if !self.can_see(team) => {
+                GameEvent::DefensiveLineupSet { team, lineup: None }
+            }
+            GameEvent::OffenseCallSet { team, .. } if !self.can_see(team) => {
+                GameEvent::OffenseCallSet { team, call: None }
+            }
+            GameEvent::DefenseCallSet { team, .. } if !self.can_see(team) => {
+                GameEvent::DefenseCallSet { team, call: None }
+            }
+            other => other,
+        }
+    }
+}
+
+impl Game {
+    pub fn seat_tokens(&self) -> &SeatTokens {
+        &self.seats
+    }
+
+    /// The seat a token belongs to, if it is one of this game's.
+    pub fn seat_for(&self, token: &str) -> Option<Seat> {
+        self.seats.seat(token)
+    }
+
+    /// Check that `seat` may perform `action` right now: only the team in possession runs
+    /// the offense, only the other team the defense, and spectators do nothing.
+    pub fn authorize(&self, seat: Seat, action: SeatAction) -> Result<(), GameError> {
+        let offense = self.state.possession;
+        let allowed = match (seat.team(), action) {
+            (None, _) => false,
+            (Some(team), SeatAction::Offense) => team == offense,
+            (Some(team), SeatAction::Defense) => team != offense,
+            (Some(_), SeatAction::Coach) => true,
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(GameError::NotAllowed(seat))
+        }
+    }
+
+    pub fn view(&self, seat: Seat) -> SeatView {
+        SeatView {
+            seat,
+            spectators_see_calls: !self.options.hide_calls,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(seat: Seat, spectators_see_calls: bool) -> SeatView {
+        SeatView {
+            seat,
+            spectators_see_calls,
+        }
+    }
+
+    #[test]
+    fn test_tokens_map_back_to_their_seats() {
+        let tokens = SeatTokens::generate();
+        assert_eq!(tokens.seat(&tokens.home), Some(Seat::Home));
+        assert_eq!(tokens.seat(&tokens.away), Some(Seat::Away));
+        assert_eq!(tokens.seat(&tokens.spectator), Some(Seat::Spectator));
+        assert_eq!(tokens.seat("not-a-token"), None);
+        assert_ne!(tokens.home, tokens.away);
+    }
+
+    #[test]
+    fn test_coaches_only_see_their_own_side() {
+        assert!(view(Seat::Home, true).can_see(GameTeams::Home));
+        assert!(!view(Seat::Home, true).can_see(GameTeams::Away));
+        assert!(view(Seat::Spectator, true).can_see(GameTeams::Away));
+        assert!(!view(Seat::Spectator, false).can_see(GameTeams::Home));
+    }
+
+    #[test]
+    fn test_redact_blanks_the_other_sides_call() {
+        let event = GameEvent::DefenseCallSet {
+            team: GameTeams::Home,
+            call: Some(crate::game::engine::DefenseCall::KickoffDefenseCall(
+                crate::game::engine::KickoffDefenseCall {
+                    onside_expected: false,
+                },
+            )),
+        };
+        match view(Seat::Away, true).redact_event(event.clone()) {
+            GameEvent::DefenseCallSet { call, .. } => assert!(call.is_none()),
+            other => panic!("expected DefenseCallSet, got {:?}", other),
+        }
+        match view(Seat::Home, true).redact_event(event) {
+            GameEvent::DefenseCallSet { call, .. } => assert!(call.is_some()),
+            other => panic!("expected DefenseCallSet, got {:?}", other),
+        }
+    }
+}
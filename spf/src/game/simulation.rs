@@ -0,0 +1,462 @@
+//! Headless simulation: plays whole games with engine-picked lineups and calls, and batches
+//! them into a [`SimulationReport`] so replays can be calibrated against real season results.
+//!
+//! Each team fields its listed starters (see [`StandardIDOffenseLineup::starters`]) on every
+//! play, and both sides' calls come from the solitaire chart (see [`Solitaire`]). Kickoffs are
+//! always kicked deep.
+//!
+//! The engine has no punt, field goal or extra point yet, so neither does a simulated game:
+//! every fourth down is gone for, and a touchdown is followed straight by the kickoff. Scores
+//! come only in touchdowns (6) and safeties (2), which is why calibration leaves points out.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::{
+    engine::{
+        defs::KICKOFF, DefenseCall, DefenseIDLineup, KickoffKind, KickoffOffenseCall, OffenseCall,
+        OffenseIDLineup, PlayStat, PlayType,
+    },
+    environment::GameEnvironment,
+    lineup::{
+        KickoffIDDefenseLineup, KickoffIDOffenseLineup, StandardIDDefenseLineup,
+        StandardIDOffenseLineup,
+    },
+    players::{Position, Roster, TeamID},
+    solitaire::Solitaire,
+    Game, GameOptions, GameTeams, Play,
+};
+
+/// Safety valve for a game that never reaches the final gun (e.g. a clock bug); a real game
+/// runs 130-180 plays.
+const MAX_PLAYS_PER_GAME: u32 = 500;
+
+/// The lineups one team fields for every play of a simulated game.
+#[derive(Debug, Clone)]
+struct TeamLineups {
+    offense: StandardIDOffenseLineup,
+    defense: StandardIDDefenseLineup,
+    kicker: KickoffIDOffenseLineup,
+    returner: KickoffIDDefenseLineup,
+}
+
+impl TeamLineups {
+    fn starters(team: &Roster) -> Result<Self, String> {
+        let first_id = |pos: Position| {
+            team.get_players(pos)
+                .first()
+                .map(|p| p.get_id())
+                .ok_or(format!("{} has no {}", team.get_team_name().name, pos))
+        };
+
+        Ok(Self {
+            offense: StandardIDOffenseLineup::starters(team).map_err(|e| e.to_string())?,
+            defense: StandardIDDefenseLineup::starters(team).map_err(|e| e.to_string())?,
+            kicker: KickoffIDOffenseLineup {
+                k: first_id(Position::K)?,
+                // The backup linebackers cover kicks.
+                coverage: team
+                    .get_players(Position::LB)
+                    .iter()
+                    .rev()
+                    .take(KICKOFF.max_coverage)
+                    .map(|p| p.get_id())
+                    .collect(),
+            },
+            returner: KickoffIDDefenseLineup {
+                kr: first_id(Position::KR)?,
+            },
+        })
+    }
+}
+
+/// One player's accumulated box-score line.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PlayerStats {
+    pub name: String,
+    pub team: String,
+    pub rush_att: u32,
+    pub rush_yds: i32,
+    pub pass_att: u32,
+    pub pass_cmp: u32,
+    pub pass_yds: i32,
+    pub pass_int: u32,
+    pub pass_td: u32,
+    pub sacked: u32,
+    pub rec: u32,
+    pub rec_yds: i32,
+    pub rec_td: u32,
+    pub def_int: u32,
+}
+
+impl PlayerStats {
+    fn add(&mut self, other: &PlayerStats) {
+        self.rush_att += other.rush_att;
+        self.rush_yds += other.rush_yds;
+        self.pass_att += other.pass_att;
+        self.pass_cmp += other.pass_cmp;
+        self.pass_yds += other.pass_yds;
+        self.pass_int += other.pass_int;
+        self.pass_td += other.pass_td;
+        self.sacked += other.sacked;
+        self.rec += other.rec;
+        self.rec_yds += other.rec_yds;
+        self.rec_td += other.rec_td;
+        self.def_int += other.def_int;
+    }
+}
+
+/// The outcome of one simulated game.
+#[derive(Debug, Clone, Serialize)]
+pub struct GameSummary {
+    pub home_score: i32,
+    pub away_score: i32,
+    pub plays: u32,
+    /// Box-score lines keyed by player id.
+    pub players: BTreeMap<String, PlayerStats>,
+}
+
+impl GameSummary {
+    fn from_game(game: &Game) -> Self {
+        let mut players: BTreeMap<String, PlayerStats> = BTreeMap::new();
+        for stat in game
+            .past_plays
+            .iter()
+            .filter_map(|p| p.result.stat.as_ref())
+        {
+            match stat {
+                PlayStat::Rush { player, yards } => {
+                    let l = player_line(&mut players, game, player);
+                    l.rush_att += 1;
+                    l.rush_yds += yards;
+                }
+                PlayStat::Pass {
+                    passer,
+                    receiver,
+                    complete,
+                    yards,
+                    touchdown,
+                } => {
+                    let qb = player_line(&mut players, game, passer);
+                    qb.pass_att += 1;
+                    if *complete {
+                        qb.pass_cmp += 1;
+                        qb.pass_yds += yards;
+                        qb.pass_td += *touchdown as u32;
+                        if let Some(r) = receiver {
+                            let rec = player_line(&mut players, game, r);
+                            rec.rec += 1;
+                            rec.rec_yds += yards;
+                            rec.rec_td += *touchdown as u32;
+                        }
+                    }
+                }
+                PlayStat::Sack { passer, .. } => {
+                    player_line(&mut players, game, passer).sacked += 1
+                }
+                PlayStat::Interception { passer, defender } => {
+                    let qb = player_line(&mut players, game, passer);
+                    qb.pass_att += 1;
+                    qb.pass_int += 1;
+                    player_line(&mut players, game, defender).def_int += 1;
+                }
+            }
+        }
+
+        Self {
+            home_score: game.state.home_score,
+            away_score: game.state.away_score,
+            plays: game.state.play_counter,
+            players,
+        }
+    }
+}
+
+/// The box-score line for a player, starting a blank one named from the game's rosters.
+fn player_line<'a>(
+    players: &'a mut BTreeMap<String, PlayerStats>,
+    game: &Game,
+    id: &String,
+) -> &'a mut PlayerStats {
+    players.entry(id.clone()).or_insert_with(|| {
+        let (team, name) = [&game.home, &game.away]
+            .iter()
+            .find_map(|r| {
+                r.get_player(id)
+                    .map(|p| (r.get_team_name().name.clone(), p.get_name()))
+            })
+            .unwrap_or_default();
+        PlayerStats {
+            name,
+            team,
+            ..PlayerStats::default()
+        }
+    })
+}
+
+impl Game {
+    /// Play the game out to the final gun with the engine choosing both teams' lineups and
+    /// calls, returning the final score and box score.
+    pub fn simulate(&mut self) -> Result<GameSummary, String> {
+        let home = TeamLineups::starters(&self.home)?;
+        let away = TeamLineups::starters(&self.away)?;
+
+        while !self.state.is_game_over() {
+            if self.state.play_counter >= MAX_PLAYS_PER_GAME {
+                return Err(format!(
+                    "Game did not finish within {} plays",
+                    MAX_PLAYS_PER_GAME
+                ));
+            }
+            let (offense, defense) = match self.state.possession {
+                GameTeams::Home => (&home, &away),
+                GameTeams::Away => (&away, &home),
+            };
+            self.coach_next_play(offense, defense)?;
+            self.run_current_play()?;
+        }
+
+        Ok(GameSummary::from_game(self))
+    }
+
+    /// Set both sides' lineups and calls for the play that is up next. Only kickoffs and
+    /// standard plays exist to coach; the extra point after a touchdown is built as a
+    /// kickoff (see [`PlayType::create_impl`]), and punts and field goals are never chosen.
+    fn coach_next_play(
+        &mut self,
+        offense: &TeamLineups,
+        defense: &TeamLineups,
+    ) -> Result<(), String> {
+        match self.next_play.as_ref().ok_or("No Play Set")?.get_type() {
+            PlayType::Kickoff => {
+                self.set_offensive_lineup_from_ids(&OffenseIDLineup::KickoffIDOffenseLineup(
+                    offense.kicker.clone(),
+                ))?;
+                self.set_defensive_lineup_from_ids(&DefenseIDLineup::KickoffIDDefenseLineup(
+                    defense.returner.clone(),
+                ))?;
+                self.set_offense_call(OffenseCall::KickoffOffenseCall(KickoffOffenseCall {
+                    kind: KickoffKind::Normal,
+                }))?;
+                Ok(())
+            }
+            PlayType::Standard => {
+                self.set_offensive_lineup_from_ids(&OffenseIDLineup::StandardIDOffenseLineup(
+                    offense.offense.clone(),
+                ))?;
+                self.set_defensive_lineup_from_ids(&DefenseIDLineup::StandardIDDefenseLineup(
+                    defense.defense.clone(),
+                ))?;
+
+                let play = match self.next_play.as_ref().ok_or("No Play Set")?.get_play() {
+                    Play::StandardPlay(p) => p,
+                    _ => return Err("Expected a standard play".to_string()),
+                };
+                let card = self.draw_card()?;
+                let off_call = Solitaire::offense_call(&card, &self.state, &play)?;
+                let card = self.draw_card()?;
+                let def_call = Solitaire::defense_call(&card, &self.state, &play)?;
+
+                self.set_offense_call(OffenseCall::StandardOffenseCall(off_call))?;
+                self.set_defense_call(DefenseCall::StandardDefenseCall(def_call))?;
+                Ok(())
+            }
+            other => Err(format!("{:?} plays can't be simulated yet", other)),
+        }
+    }
+}
+
+/// Spread of one team's final scores over a batch.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScoreDistribution {
+    pub mean: f64,
+    pub min: i32,
+    pub max: i32,
+    /// How many games ended with each score.
+    pub counts: BTreeMap<i32, u32>,
+}
+
+impl ScoreDistribution {
+    fn from_scores(scores: &[i32]) -> Self {
+        if scores.is_empty() {
+            return Self::default();
+        }
+        let mut counts = BTreeMap::new();
+        for s in scores {
+            *counts.entry(*s).or_insert(0) += 1;
+        }
+        Self {
+            mean: scores.iter().sum::<i32>() as f64 / scores.len() as f64,
+            min: *scores.iter().min().unwrap(),
+            max: *scores.iter().max().unwrap(),
+            counts,
+        }
+    }
+}
+
+/// Aggregate results of a batch of simulated games between the same two teams.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationReport {
+    pub home: TeamID,
+    pub away: TeamID,
+    pub games: u32,
+    /// Seed of the first game; game `n` is played with `seed + n`.
+    pub seed: u64,
+    pub home_wins: u32,
+    pub away_wins: u32,
+    pub ties: u32,
+    pub home_score: ScoreDistribution,
+    pub away_score: ScoreDistribution,
+    /// Box-score totals over the whole batch, keyed by player id. Divide by `games` for
+    /// per-game averages.
+    pub players: BTreeMap<String, PlayerStats>,
+}
+
+impl SimulationReport {
+    fn from_summaries(home: &TeamID, away: &TeamID, seed: u64, summaries: &[GameSummary]) -> Self {
+        let home_scores: Vec<i32> = summaries.iter().map(|s| s.home_score).collect();
+        let away_scores: Vec<i32> = summaries.iter().map(|s| s.away_score).collect();
+
+        let mut players: BTreeMap<String, PlayerStats> = BTreeMap::new();
+        for (id, line) in summaries.iter().flat_map(|s| s.players.iter()) {
+            players
+                .entry(id.clone())
+                .or_insert_with(|| PlayerStats {
+                    name: line.name.clone(),
+                    team: line.team.clone(),
+                    ..PlayerStats::default()
+                })
+                .add(line);
+        }
+
+        Self {
+            home: home.clone(),
+            away: away.clone(),
+            games: summaries.len() as u32,
+            seed,
+            home_wins: summaries
+                .iter()
+                .filter(|s| s.home_score > s.away_score)
+                .count() as u32,
+            away_wins: summaries
+                .iter()
+                .filter(|s| s.away_score > s.home_score)
+                .count() as u32,
+            ties: summaries
+                .iter()
+                .filter(|s| s.home_score == s.away_score)
+                .count() as u32,
+            home_score: ScoreDistribution::from_scores(&home_scores),
+            away_score: ScoreDistribution::from_scores(&away_scores),
+            players,
+        }
+    }
+}
+
+/// Simulate `games` games between two teams of the environment's league. Each game gets its
+/// own deck shuffled from `seed + n`, so the same seed reproduces the same batch.
+pub fn simulate_games(
+    env: &GameEnvironment,
+    home: &TeamID,
+    away: &TeamID,
+    games: u32,
+    seed: u64,
+    options: GameOptions,
+) -> Result<SimulationReport, String> {
+    let summaries = (0..games)
+        .map(|n| {
+            simulate_game(
+                env,
+                home,
+                away,
+                seed.wrapping_add(n as u64),
+                options.clone(),
+            )
+        })
+        .collect::<Result<Vec<GameSummary>, String>>()?;
+
+    Ok(SimulationReport::from_summaries(
+        home, away, seed, &summaries,
+    ))
+}
+
+/// Simulate a single game between two teams of the environment's league, shuffling its deck
+/// from `seed`.
+pub fn simulate_game(
+    env: &GameEnvironment,
+    home: &TeamID,
+    away: &TeamID,
+    seed: u64,
+    options: GameOptions,
+) -> Result<GameSummary, String> {
+    let home_roster = env
+        .roster(home)
+        .ok_or_else(|| format!("Unknown team: {}", home.to_string()))?;
+    let away_roster = env
+        .roster(away)
+        .ok_or_else(|| format!("Unknown team: {}", away.to_string()))?;
+
+    Game::build(
+        home_roster,
+        away_roster,
+        env.new_deck().with_seed(seed),
+        options,
+    )
+    .simulate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{players::Player, testutils};
+
+    #[test]
+    fn test_score_distribution_counts_scores() {
+        let dist = ScoreDistribution::from_scores(&[14, 21, 14, 3]);
+        assert_eq!(dist.min, 3);
+        assert_eq!(dist.max, 21);
+        assert_eq!(dist.mean, 13.0);
+        assert_eq!(dist.counts.get(&14), Some(&2));
+        assert_eq!(dist.counts.get(&7), None);
+    }
+
+    #[test]
+    fn test_starters_need_a_full_roster() {
+        let empty = Roster::from_players(
+            TeamID {
+                name: "Empty".into(),
+                year: "1983".into(),
+            },
+            Vec::<Player>::new(),
+        );
+        assert!(TeamLineups::starters(&empty).is_err());
+    }
+
+    // Plays real games end to end, so it needs the converted league and the FAC deck.
+    #[test]
+    fn test_simulated_batch_is_reproducible_from_seed() {
+        let Some(env) = testutils::league_environment() else {
+            return;
+        };
+        let team = |name: &str| TeamID {
+            name: name.into(),
+            year: "1983".into(),
+        };
+        let (home, away) = (team("Chicago"), team("Detroit"));
+
+        let first = simulate_games(&env, &home, &away, 2, 1983, GameOptions::default())
+            .expect("games simulate to completion");
+        let second = simulate_games(&env, &home, &away, 2, 1983, GameOptions::default())
+            .expect("games simulate to completion");
+
+        assert_eq!(first.games, 2);
+        assert_eq!(first.home_wins + first.away_wins + first.ties, 2);
+        assert_eq!(first.home_score.counts, second.home_score.counts);
+        assert_eq!(first.away_score.counts, second.away_score.counts);
+        assert!(
+            first.players.values().any(|p| p.rush_att > 0),
+            "someone carried the ball"
+        );
+    }
+}
@@ -0,0 +1,393 @@
+//! Solitaire play calling: lets the engine call plays for one side using the FAC deck.
+//!
+//! Every FAC carries a `solitaire` column such as `1.P(x2)/2.R(NK)/3.R(NK)/4.PR/5.P` — one
+//! defensive call for each of the five down-and-distance situations on the solitaire chart.
+//! The defensive call is read straight from that column. The board game has no offensive
+//! column, so the offensive call is chosen from the situation and the card's run number.
+
+use std::str::FromStr;
+
+use super::{
+    engine::{
+        defs::{DEFENSE_STRATEGY_LIMITS, GROUNDING_YARDS, RED_ZONE},
+        Down,
+    },
+    fac::{FacCard, FacData, FacManager},
+    lineup::{DefensiveBox, DefensiveRow, OffensiveBox, StandardDefensiveLineup},
+    standard_play::{
+        DefensivePlay, DefensiveStrategy, OffensivePlayType, OffensiveStrategy,
+        StandardDefenseCall, StandardOffenseCall, StandardPlay,
+    },
+    GameState,
+};
+
+/// Number of consecutive Z cards tolerated before giving up on a solitaire draw.
+const MAX_Z_DRAWS: usize = 10;
+
+/// The five rows of the solitaire chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolitaireSituation {
+    /// 1st down.
+    FirstDown,
+    /// 2nd down with 4-6 yards to go.
+    Medium,
+    /// 2nd down with 7+ to go, or 3rd/4th down with 4-6 to go.
+    Long,
+    /// 3rd/4th down with 7+ to go.
+    VeryLong,
+    /// 2nd-4th down with 1-3 yards to go.
+    Short,
+}
+
+impl SolitaireSituation {
+    pub fn from_state(state: &GameState) -> Self {
+        let to_go = state.yards_to_go();
+        match state.down {
+            Down::First => SolitaireSituation::FirstDown,
+            _ if to_go <= 3 => SolitaireSituation::Short,
+            Down::Second if to_go <= 6 => SolitaireSituation::Medium,
+            Down::Second => SolitaireSituation::Long,
+            _ if to_go <= 6 => SolitaireSituation::Long,
+            _ => SolitaireSituation::VeryLong,
+        }
+    }
+
+    /// The 1-based entry in the FAC `solitaire` column for this situation.
+    fn column_index(&self) -> usize {
+        match self {
+            SolitaireSituation::FirstDown => 1,
+            SolitaireSituation::Medium => 2,
+            SolitaireSituation::Long => 3,
+            SolitaireSituation::VeryLong => 4,
+            SolitaireSituation::Short => 5,
+        }
+    }
+}
+
+/// One entry of the solitaire column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolitaireDefense {
+    /// `R(NK)` - run defense, no key.
+    RunNoKey,
+    /// `R(BC)` - run defense keyed on the ball carrier.
+    RunKeyBallCarrier,
+    /// `P` / `P(x2)` - pass defense, optionally double covering.
+    Pass { double_cover: bool },
+    /// `PR` / `PR(x2)` - prevent defense, optionally double covering.
+    Prevent { double_cover: bool },
+    /// `BLZ` - blitz.
+    Blitz,
+}
+
+impl FromStr for SolitaireDefense {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, String> {
+        match input.trim() {
+            "R(NK)" => Ok(SolitaireDefense::RunNoKey),
+            "R(BC)" => Ok(SolitaireDefense::RunKeyBallCarrier),
+            "P" => Ok(SolitaireDefense::Pass {
+                double_cover: false,
+            }),
+            "P(x2)" => Ok(SolitaireDefense::Pass { double_cover: true }),
+            "PR" => Ok(SolitaireDefense::Prevent {
+                double_cover: false,
+            }),
+            "PR(x2)" => Ok(SolitaireDefense::Prevent { double_cover: true }),
+            "BLZ" => Ok(SolitaireDefense::Blitz),
+            _ => Err(format!("Invalid solitaire call: {}", input)),
+        }
+    }
+}
+
+impl SolitaireDefense {
+    /// Parse the entry for `situation` out of a full `solitaire` column string.
+    pub fn from_column(column: &str, situation: SolitaireSituation) -> Result<Self, String> {
+        let wanted = situation.column_index().to_string();
+        column
+            .split('/')
+            .filter_map(|entry| entry.split_once('.'))
+            .find(|(idx, _)| idx.trim() == wanted)
+            .ok_or(format!("No solitaire entry {} in {}", wanted, column))
+            .and_then(|(_, call)| SolitaireDefense::from_str(call))
+    }
+}
+
+pub struct Solitaire {}
+impl Solitaire {
+    /// Draw the next data card for a solitaire call, skipping Z cards.
+    pub fn draw_card(deck: &mut FacManager) -> Result<FacData, String> {
+        for _ in 0..MAX_Z_DRAWS {
+            if let FacCard::Data(card) = deck.get_fac(false) {
+                return Ok(card);
+            }
+        }
+        Err("No FAC available for solitaire call".to_string())
+    }
+
+    /// Build the defensive call for the current play from the card's solitaire column.
+    ///
+    /// Calls the lineup can't legally support (extra coverage without a Box L player or with
+    /// the wrong row counts, a blitz without enough linebackers) fall back to the plain call.
+    pub fn defense_call(
+        card: &FacData,
+        state: &GameState,
+        play: &StandardPlay,
+    ) -> Result<StandardDefenseCall, String> {
+        let lineup = play.defense.as_ref().ok_or("Set lineup before Call")?;
+        let situation = SolitaireSituation::from_state(state);
+        let sol = SolitaireDefense::from_column(&card.solitaire, situation)?;
+
+        let straight = |defense_type| StandardDefenseCall {
+            defense_type,
+            strategy: DefensiveStrategy::Straight,
+            key: None,
+            def_players: vec![],
+        };
+
+        let call = match sol {
+            SolitaireDefense::RunNoKey => straight(DefensivePlay::RunDefense),
+            SolitaireDefense::RunKeyBallCarrier => StandardDefenseCall {
+                key: Solitaire::ball_carrier(play),
+                ..straight(DefensivePlay::RunDefense)
+            },
+            SolitaireDefense::Pass { double_cover } => {
+                Solitaire::coverage(DefensivePlay::PassDefense, double_cover, state, play)
+                    .unwrap_or(straight(DefensivePlay::PassDefense))
+            }
+            SolitaireDefense::Prevent { double_cover } => {
+                Solitaire::coverage(DefensivePlay::PreventDefense, double_cover, state, play)
+                    .unwrap_or(straight(DefensivePlay::PreventDefense))
+            }
+            SolitaireDefense::Blitz => {
+                let blitzers = Solitaire::blitzers(lineup);
+                if blitzers.len() < 2 {
+                    straight(DefensivePlay::PassDefense)
+                } else {
+                    StandardDefenseCall {
+                        def_players: blitzers,
+                        ..straight(DefensivePlay::Blitz)
+                    }
+                }
+            }
+        };
+
+        Ok(call)
+    }
+
+    /// Build the offensive call for the current play from the situation and run number.
+    pub fn offense_call(
+        card: &FacData,
+        state: &GameState,
+        play: &StandardPlay,
+    ) -> Result<StandardOffenseCall, String> {
+        let lineup = play
+            .offense
+            .as_ref()
+            .ok_or("Set Lineup before setting Call")?;
+        let rn = card.run_num.num;
+
+        // Highest run number that still calls a run in this situation.
+        let run_cutoff = match SolitaireSituation::from_state(state) {
+            SolitaireSituation::Short => 8,
+            SolitaireSituation::FirstDown => 6,
+            SolitaireSituation::Medium => 5,
+            SolitaireSituation::Long => 3,
+            SolitaireSituation::VeryLong => 1,
+        };
+
+        let backs = [OffensiveBox::B1, OffensiveBox::B2, OffensiveBox::B3];
+        let ball_carrier = backs
+            .iter()
+            .find(|b| lineup.get_player_in_pos(b).is_some())
+            .copied();
+
+        if let (true, Some(target)) = (rn <= run_cutoff, ball_carrier) {
+            let play_type = match rn % 4 {
+                0 => OffensivePlayType::SL,
+                1 => OffensivePlayType::IL,
+                2 => OffensivePlayType::IR,
+                _ => OffensivePlayType::SR,
+            };
+            // Any other back leads the way.
+            let blocking_back = backs
+                .iter()
+                .find(|b| **b != target && lineup.get_player_in_pos(b).is_some())
+                .copied();
+            return Ok(StandardOffenseCall {
+                play_type,
+                strategy: OffensiveStrategy::NoStrategy,
+                target,
+                blocking_back,
+                throw_away: false,
+            });
+        }
+
+        let play_type = match rn {
+            n if n <= 6 => OffensivePlayType::QK,
+            n if n <= 10 => OffensivePlayType::SH,
+            // No room to go deep inside the opponent's 20.
+            _ if state.yards_to_goal() <= RED_ZONE.start => OffensivePlayType::SH,
+            _ => OffensivePlayType::LG,
+        };
+        let receivers: Vec<OffensiveBox> = OffensiveBox::get_receiver_spots()
+            .into_iter()
+            .filter(|b| lineup.get_player_in_pos(b).is_some())
+            .collect();
+        let target = *receivers
+            .get(rn as usize % receivers.len().max(1))
+            .ok_or("No eligible receiver for solitaire call")?;
+
+        Ok(StandardOffenseCall {
+            play_type,
+            strategy: OffensiveStrategy::NoStrategy,
+            target,
+            blocking_back: None,
+            // Backed up near his own goal line, the QB won't risk a safety.
+            throw_away: state.yard_line <= GROUNDING_YARDS,
+        })
+    }
+
+    /// The back the offense has called a run for, if the offense call is already known.
+    fn ball_carrier(play: &StandardPlay) -> Option<OffensiveBox> {
+        let call = play.offense_call.as_ref()?;
+        match call.target {
+            OffensiveBox::B1 | OffensiveBox::B2 | OffensiveBox::B3 => Some(call.target),
+            _ => None,
+        }
+    }
+
+    /// A double-coverage call keyed on the likely receiver, when the lineup allows one.
+    fn coverage(
+        defense_type: DefensivePlay,
+        double_cover: bool,
+        state: &GameState,
+        play: &StandardPlay,
+    ) -> Option<StandardDefenseCall> {
+        if !double_cover || !state.rules.is_advanced() {
+            return None;
+        }
+        let lineup = play.defense.as_ref()?;
+
+        let row2 = lineup.get_count_in_row(DefensiveRow::Row2);
+        let row3 = lineup.get_count_in_row(DefensiveRow::Row3);
+        let legal_rows = DEFENSE_STRATEGY_LIMITS
+            .get(&DefensiveStrategy::DoubleCover)?
+            .iter()
+            .any(|x| x.row2 == row2 && x.row3 == row3);
+        let extra = lineup.get_players_in_pos(&DefensiveBox::BoxL);
+        if !legal_rows || extra.is_empty() {
+            return None;
+        }
+
+        let called = play.offense_call.as_ref().map(|c| c.target);
+        let key = match called {
+            Some(t) if OffensiveBox::get_receiver_spots().contains(&t) => t,
+            _ => OffensiveBox::FL1,
+        };
+
+        Some(StandardDefenseCall {
+            defense_type,
+            strategy: DefensiveStrategy::DoubleCover,
+            key: Some(key),
+            def_players: vec![extra[0].get_id()],
+        })
+    }
+
+    /// Up to five row-two linebackers to send on a blitz.
+    fn blitzers(lineup: &StandardDefensiveLineup) -> Vec<String> {
+        [
+            DefensiveBox::BoxF,
+            DefensiveBox::BoxG,
+            DefensiveBox::BoxH,
+            DefensiveBox::BoxI,
+            DefensiveBox::BoxJ,
+        ]
+        .iter()
+        .flat_map(|b| lineup.get_players_in_pos(b))
+        .map(|p| p.get_id())
+        .take(5)
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(down: Down, to_go: i32) -> GameState {
+        GameState {
+            down,
+            yard_line: 40,
+            first_down_target: 40 + to_go,
+            ..GameState::start_state()
+        }
+    }
+
+    #[test]
+    fn test_situation_from_down_and_distance() {
+        let cases = [
+            (Down::First, 10, SolitaireSituation::FirstDown),
+            (Down::First, 2, SolitaireSituation::FirstDown),
+            (Down::Second, 2, SolitaireSituation::Short),
+            (Down::Second, 5, SolitaireSituation::Medium),
+            (Down::Second, 9, SolitaireSituation::Long),
+            (Down::Third, 1, SolitaireSituation::Short),
+            (Down::Third, 6, SolitaireSituation::Long),
+            (Down::Third, 12, SolitaireSituation::VeryLong),
+            (Down::Fourth, 3, SolitaireSituation::Short),
+            (Down::Fourth, 8, SolitaireSituation::VeryLong),
+        ];
+
+        for (down, to_go, expected) in cases {
+            assert_eq!(
+                SolitaireSituation::from_state(&state(down, to_go)),
+                expected,
+                "{:?} and {}",
+                down,
+                to_go
+            );
+        }
+    }
+
+    #[test]
+    fn test_solitaire_defense_from_column_picks_situation_entry() {
+        let column = "1.P(x2)/2.R(NK)/3.R(BC)/4.PR/5.BLZ";
+        let cases = [
+            (
+                SolitaireSituation::FirstDown,
+                SolitaireDefense::Pass { double_cover: true },
+            ),
+            (SolitaireSituation::Medium, SolitaireDefense::RunNoKey),
+            (
+                SolitaireSituation::Long,
+                SolitaireDefense::RunKeyBallCarrier,
+            ),
+            (
+                SolitaireSituation::VeryLong,
+                SolitaireDefense::Prevent {
+                    double_cover: false,
+                },
+            ),
+            (SolitaireSituation::Short, SolitaireDefense::Blitz),
+        ];
+
+        for (situation, expected) in cases {
+            assert_eq!(
+                SolitaireDefense::from_column(column, situation).unwrap(),
+                expected,
+                "{:?}",
+                situation
+            );
+        }
+    }
+
+    #[test]
+    fn test_solitaire_defense_rejects_unknown_call() {
+        assert!(SolitaireDefense::from_str("XX").is_err());
+        assert!(
+            SolitaireDefense::from_column("1.P/2.P", SolitaireSituation::Short).is_err(),
+            "a column without the situation's entry must error"
+        );
+    }
+}
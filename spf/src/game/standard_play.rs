@@ -1,18 +1,21 @@
 use enum_as_inner::EnumAsInner;
 use serde_derive::{Deserialize, Serialize};
+use spf_core::trace;
 use utoipa::ToSchema;
 
 use crate::game::{
     lineup::{StandardDefensiveLineup, StandardOffensiveLineup},
-    players::Roster,
-    GameState, Play,
+    players::{Roster, TeamStats},
+    GameState, GameTeams, Play, RulesLevel,
 };
 
 use super::{
     engine::{
-        defs::{DEFENSE_STRATEGY_LIMITS, OFFENSIVE_PLAYS_LIST},
+        clockplay::ClockUtils,
+        defs::{DEFENSE_STRATEGY_LIMITS, OFFENSIVE_PLAYS_LIST, QB_SNEAK, RED_ZONE},
         CardStreamer,
     },
+    error::GameError,
     fac::{FacData, PassTarget, RunDirection},
     lineup::{DefensiveBox, DefensiveRow, OffensiveBox},
     players::QBStats,
@@ -32,7 +35,7 @@ type PlayRunner = for<'a> fn(&'a GameState, PlaySetup<'a>, &'a mut CardStreamer<
 pub use spf_core::shiftable::{PassResult, PassRushResult};
 
 pub trait Validatable {
-    fn validate(&self, play: &StandardPlay) -> Result<(), String>;
+    fn validate(&self, play: &StandardPlay, state: &GameState) -> Result<(), GameError>;
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -85,9 +88,13 @@ pub enum OffensivePlayType {
 pub enum OffensiveStrategy {
     NoStrategy,
     Sneak,
+    /// The quarterback takes a knee; the play type and target are ignored.
     Flop,
     Draw,
     PlayAction,
+    /// The quarterback spikes the ball to stop the clock; the play type and target are
+    /// ignored.
+    Spike,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
@@ -114,13 +121,25 @@ pub struct StandardDefenseCall {
     pub def_players: Vec<String>,
 }
 impl StandardDefenseCall {
+    /// Under the basic rules every defense plays it straight.
+    pub fn check_rules(&self, rules: RulesLevel) -> Result<(), GameError> {
+        if !rules.is_advanced() && self.strategy != DefensiveStrategy::Straight {
+            return Err(GameError::InvalidCall(format!(
+                "{:?} is only available with advanced rules",
+                self.strategy
+            )));
+        }
+        Ok(())
+    }
+
     fn validate_def_player(
         &self,
         lineup: &StandardDefensiveLineup,
         player: &String,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         let pos = lineup.find_player(player);
-        let actual = pos.ok_or(format!("{} is not in lineup", player))?;
+        let actual =
+            pos.ok_or_else(|| GameError::InvalidCall(format!("{} is not in lineup", player)))?;
         if matches!(
             self.strategy,
             DefensiveStrategy::DoubleCover
@@ -128,16 +147,24 @@ impl StandardDefenseCall {
                 | DefensiveStrategy::TripleCover
         ) {
             if actual != DefensiveBox::BoxL {
-                return Err("Extra Coverage player must bein Box L".to_string());
+                return Err(GameError::InvalidCall(
+                    "Extra Coverage player must bein Box L".to_string(),
+                ));
             }
         }
         return Ok(());
     }
 }
 impl Validatable for StandardDefenseCall {
-    fn validate(&self, play: &StandardPlay) -> Result<(), String> {
-        println!("Validating Defense Lineup");
-        let lineup = play.defense.as_ref().ok_or("Set lineup before Call")?;
+    fn validate(&self, play: &StandardPlay, state: &GameState) -> Result<(), GameError> {
+        trace!("Validating Defense Lineup");
+        let lineup = play
+            .defense
+            .as_ref()
+            .ok_or_else(|| GameError::NotReady("Set lineup before Call".to_string()))?;
+
+        self.check_rules(state.rules)?;
+
         self.def_players
             .iter()
             .try_for_each(|id| self.validate_def_player(lineup, id))?;
@@ -145,7 +172,9 @@ impl Validatable for StandardDefenseCall {
         if self.defense_type == DefensivePlay::Blitz
             && (self.def_players.len() < 2 || self.def_players.len() > 5)
         {
-            return Err("Must blitz between 2 and 5 players".to_string());
+            return Err(GameError::InvalidCall(
+                "Must blitz between 2 and 5 players".to_string(),
+            ));
         }
 
         let vals_opt = DEFENSE_STRATEGY_LIMITS.get(&self.strategy);
@@ -154,10 +183,10 @@ impl Validatable for StandardDefenseCall {
             let row3 = lineup.get_count_in_row(DefensiveRow::Row3);
             let exists = vals.iter().any(|x| row2 == x.row2 && row3 == x.row3);
             if !exists {
-                return Err(format!(
+                return Err(GameError::InvalidCall(format!(
                     "Row2: {} Row3: {} - illegal lineup for {:?}",
                     row2, row3, self.strategy
-                ));
+                )));
             }
         }
 
@@ -168,9 +197,14 @@ impl Validatable for StandardDefenseCall {
         ])
         .contains(&self.strategy)
         {
-            let key = self.key.ok_or("Extra coverage needs to key a player")?;
+            let key = self.key.ok_or_else(|| {
+                GameError::InvalidCall("Extra coverage needs to key a player".to_string())
+            })?;
             if !OffensiveBox::get_receiver_spots().contains(&key) {
-                return Err(format!("{:?} not a position for extra coverage", key));
+                return Err(GameError::InvalidCall(format!(
+                    "{:?} not a position for extra coverage",
+                    key
+                )));
             }
         }
         return Ok(());
@@ -182,24 +216,70 @@ pub struct StandardOffenseCall {
     pub play_type: OffensivePlayType,
     pub strategy: OffensiveStrategy,
     pub target: OffensiveBox,
+    /// On a run, another back who leads the ball carrier; he blocks whenever the FAC says
+    /// `BK`. Without one, `BK` is the back in B1.
+    #[serde(default)]
+    pub blocking_back: Option<OffensiveBox>,
+    /// On a dropback pass, throw the ball away rather than take a sack: intentional
+    /// grounding instead of the sack yardage.
+    #[serde(default)]
+    pub throw_away: bool,
 }
 
 impl Validatable for StandardOffenseCall {
-    fn validate(&self, play: &StandardPlay) -> Result<(), String> {
+    fn validate(&self, play: &StandardPlay, state: &GameState) -> Result<(), GameError> {
+        if self.is_clock_play() {
+            play.offense
+                .as_ref()
+                .ok_or_else(|| GameError::NotReady("Set Lineup before setting Call".to_string()))?;
+            return Ok(());
+        }
+
         let meta = get_offensive_play_info(&self.play_type);
         if !meta.allowed_targets.contains(&self.target) {
-            return Err(format!(
+            return Err(GameError::InvalidCall(format!(
                 "{:?} is not a valid target for {:?}",
                 self.target, self.play_type
-            ));
+            )));
         }
 
         let off: &StandardOffensiveLineup = play
             .offense
             .as_ref()
-            .ok_or("Set Lineup before setting Call")?;
+            .ok_or_else(|| GameError::NotReady("Set Lineup before setting Call".to_string()))?;
         off.get_player_in_pos(&self.target)
-            .ok_or(format!("No player in {:?}", self.target))?;
+            .ok_or_else(|| GameError::InvalidCall(format!("No player in {:?}", self.target)))?;
+
+        if let Some(back) = self.blocking_back {
+            self.validate_blocking_back(back, meta, off)?;
+        }
+
+        if self.throw_away
+            && !matches!(
+                self.play_type,
+                OffensivePlayType::QK | OffensivePlayType::SH | OffensivePlayType::LG
+            )
+        {
+            return Err(GameError::InvalidCall(format!(
+                "Nothing to throw away on a {:?}",
+                self.play_type
+            )));
+        }
+
+        if self.play_type == OffensivePlayType::LG && state.yards_to_goal() <= RED_ZONE.start {
+            return Err(GameError::InvalidCall(format!(
+                "No room for a long pass inside the opponent's {}",
+                RED_ZONE.start
+            )));
+        }
+
+        self.check_rules(state.rules)?;
+
+        if self.target == OffensiveBox::QB && matches!(self.strategy, OffensiveStrategy::Draw) {
+            return Err(GameError::InvalidCall(
+                "A draw is handed off to a back".to_string(),
+            ));
+        }
 
         match self.strategy {
             OffensiveStrategy::Draw => {
@@ -217,8 +297,25 @@ impl Validatable for StandardOffenseCall {
                 )?;
             }
             OffensiveStrategy::NoStrategy => {}
-            OffensiveStrategy::Sneak => {}
-            OffensiveStrategy::Flop => {}
+            OffensiveStrategy::Sneak => {
+                validate_strategy(
+                    "Sneak",
+                    &self.play_type,
+                    vec![OffensivePlayType::IL, OffensivePlayType::IR],
+                )?;
+                if self.target != OffensiveBox::QB {
+                    return Err(GameError::InvalidCall(
+                        "Only the quarterback can sneak".to_string(),
+                    ));
+                }
+                if state.yards_to_go() > QB_SNEAK.max_to_go {
+                    return Err(GameError::InvalidCall(format!(
+                        "A sneak needs {} yards or less to go",
+                        QB_SNEAK.max_to_go
+                    )));
+                }
+            }
+            OffensiveStrategy::Flop | OffensiveStrategy::Spike => {}
         }
 
         // use player for further validations
@@ -226,13 +323,72 @@ impl Validatable for StandardOffenseCall {
     }
 }
 
+impl StandardOffenseCall {
+    /// Draws and play action are advanced-rules strategies. Needs no lineup, so the game
+    /// can refuse the call as soon as it's made.
+    pub fn check_rules(&self, rules: RulesLevel) -> Result<(), GameError> {
+        if !rules.is_advanced()
+            && matches!(
+                self.strategy,
+                OffensiveStrategy::Draw | OffensiveStrategy::PlayAction
+            )
+        {
+            return Err(GameError::InvalidCall(format!(
+                "{:?} is only available with advanced rules",
+                self.strategy
+            )));
+        }
+        Ok(())
+    }
+
+    /// A flop or spike: no FAC, just time off (or kept on) the clock.
+    pub fn is_clock_play(&self) -> bool {
+        matches!(
+            self.strategy,
+            OffensiveStrategy::Flop | OffensiveStrategy::Spike
+        )
+    }
+
+    /// A run the quarterback keeps by design; sneaks and flops don't count.
+    pub fn is_designed_qb_run(&self) -> bool {
+        self.target == OffensiveBox::QB && self.strategy == OffensiveStrategy::NoStrategy
+    }
+
+    fn validate_blocking_back(
+        &self,
+        back: OffensiveBox,
+        meta: &OffensivePlayInfo,
+        off: &StandardOffensiveLineup,
+    ) -> Result<(), GameError> {
+        if meta.play_type.as_run().is_none() {
+            return Err(GameError::InvalidCall(format!(
+                "No blocking back on a {:?}",
+                self.play_type
+            )));
+        }
+        let backs = [OffensiveBox::B1, OffensiveBox::B2, OffensiveBox::B3];
+        if !backs.contains(&back) || back == self.target {
+            return Err(GameError::InvalidCall(format!(
+                "{:?} can't block for the ball carrier in {:?}",
+                back, self.target
+            )));
+        }
+        off.get_player_in_pos(&back)
+            .ok_or_else(|| GameError::InvalidCall(format!("No player in {:?}", back)))?;
+        Ok(())
+    }
+}
+
 fn validate_strategy(
     strategy: &str,
     actual: &OffensivePlayType,
     allowed: Vec<OffensivePlayType>,
-) -> Result<(), String> {
+) -> Result<(), GameError> {
     if !allowed.contains(actual) {
-        return Err(format!("{:?} can not be played on {:?}", strategy, actual));
+        return Err(GameError::InvalidCall(format!(
+            "{:?} can not be played on {:?}",
+            strategy, actual
+        )));
     }
     Ok(())
 }
@@ -248,6 +404,8 @@ pub struct PlaySetup<'a> {
     pub defense: StandardDefensiveLineup,
     pub defense_call: &'a StandardDefenseCall,
     pub offense_metadata: &'a OffensivePlayInfo,
+    /// The offense's big-play rating where this game is being played.
+    pub big_play: i32,
 }
 
 #[derive(Debug, Default, Clone, Serialize)]
@@ -256,33 +414,49 @@ pub struct StandardPlay {
     pub offense_call: Option<StandardOffenseCall>,
     pub defense: Option<StandardDefensiveLineup>,
     pub defense_call: Option<StandardDefenseCall>,
+    pub offense_card: Option<TeamStats>,
+}
+
+impl StandardPlay {
+    /// The offense's home rating in its own stadium, its road rating anywhere else.
+    fn get_big_play_rating(&self, state: &GameState) -> i32 {
+        match (&self.offense_card, state.possession) {
+            (None, _) => 0,
+            (Some(card), GameTeams::Home) if !state.neutral_site => card.big_play_home,
+            (Some(card), _) => card.big_play_road,
+        }
+    }
 }
 
 impl PlayImpl for StandardPlay {
-    fn validate(&self) -> Result<(), String> {
-        println!("Validating Play:");
-        let _ = self.offense.as_ref().ok_or("Offense not set");
-        let _ = self.defense.as_ref().ok_or("Defense not set");
-        let offense_call = self.offense_call.as_ref().ok_or("Offense Call  not set")?;
-        offense_call.validate(self)?;
-        let defense_call = self.defense_call.as_ref().ok_or("Defense Call  not set")?;
-        defense_call.validate(self)?;
+    fn validate(&self, game_state: &GameState) -> Result<(), GameError> {
+        trace!("Validating Play:");
+        let offense_call = self
+            .offense_call
+            .as_ref()
+            .ok_or_else(|| GameError::NotReady("Offense Call  not set".to_string()))?;
+        offense_call.validate(self, game_state)?;
+        let defense_call = self
+            .defense_call
+            .as_ref()
+            .ok_or_else(|| GameError::NotReady("Defense Call  not set".to_string()))?;
+        defense_call.validate(self, game_state)?;
         Ok(()) // offense.is_legal_lineup()?;
     }
 
-    fn set_offense_call(&mut self, call: OffenseCall) -> Result<(), String> {
-        println!("Offense Call {:?}", call);
+    fn set_offense_call(&mut self, call: OffenseCall) -> Result<(), GameError> {
+        trace!("Offense Call {:?}", call);
         let c = call
             .as_standard_offense_call()
-            .ok_or("Bad type".to_string())?;
+            .ok_or(GameError::WrongPlayType(PlayType::Standard))?;
         self.offense_call = Some(c.clone());
         Ok(())
     }
 
-    fn set_defense_call(&mut self, call: DefenseCall) -> Result<(), String> {
+    fn set_defense_call(&mut self, call: DefenseCall) -> Result<(), GameError> {
         let c = call
             .as_standard_defense_call()
-            .ok_or("Bad type".to_string())?;
+            .ok_or(GameError::WrongPlayType(PlayType::Standard))?;
         self.defense_call = Some(c.clone());
         Ok(())
     }
@@ -291,12 +465,13 @@ impl PlayImpl for StandardPlay {
         &mut self,
         lineup: &OffenseIDLineup,
         roster: &Roster,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         let l = lineup
             .as_standard_id_offense_lineup()
-            .ok_or("Bad type".to_string())?;
+            .ok_or(GameError::WrongPlayType(PlayType::Standard))?;
 
         self.offense = Some(StandardOffensiveLineup::create_lineup(l, roster)?);
+        self.offense_card = roster.team_card().cloned();
 
         self.offense.as_ref().unwrap().is_legal_lineup()?;
 
@@ -307,10 +482,10 @@ impl PlayImpl for StandardPlay {
         &mut self,
         lineup: &DefenseIDLineup,
         roster: &Roster,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         let l = lineup
             .as_standard_id_defense_lineup()
-            .ok_or("Bad type".to_string())?;
+            .ok_or(GameError::WrongPlayType(PlayType::Standard))?;
 
         self.defense = Some(StandardDefensiveLineup::create_lineup(l, roster)?);
 
@@ -344,9 +519,15 @@ impl PlayImpl for StandardPlay {
             offense_call: self.offense_call.as_ref().unwrap(),
             defense: real_def,
             defense_call: def_call,
+            big_play: self.get_big_play_rating(game_state),
         };
 
-        (details.offense_metadata.handler)(game_state, details, card_streamer)
+        let handler: PlayRunner = match details.offense_call.strategy {
+            OffensiveStrategy::Flop => ClockUtils::handle_flop,
+            OffensiveStrategy::Spike => ClockUtils::handle_spike,
+            _ => details.offense_metadata.handler,
+        };
+        handler(game_state, details, card_streamer)
     }
 
     fn get_play(&self) -> Play {
@@ -359,6 +540,14 @@ impl PlayImpl for StandardPlay {
 }
 
 impl StandardPlay {
+    /// The id of the quarterback in the offensive lineup, if it is set.
+    pub fn qb_id(&self) -> Option<String> {
+        self.offense
+            .as_ref()?
+            .get_player_in_pos(&OffensiveBox::QB)
+            .map(|qb| qb.get_id())
+    }
+
     pub fn new() -> Self {
         return Self {
             ..Default::default()
@@ -369,3 +558,135 @@ impl StandardPlay {
         return result.clone();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        engine::defs::BIG_PLAY,
+        stats::Range,
+        testutils::{
+            card, defense_call, offense_call, pass_card, roster, run, said, standard_play, state,
+            team,
+        },
+    };
+
+    /// The home squad with a team card rating its big plays 2 at home and 1 on the road.
+    fn rated() -> Roster {
+        roster("Home").with_team_card(Some(TeamStats {
+            team: team("Home"),
+            big_play_home: 2,
+            big_play_road: 1,
+            fumbles_lost: Range { start: 1, end: 1 },
+            def_adj: 0,
+        }))
+    }
+
+    /// A 6-yard handoff by `offense` from `state`, the big play chance read off `big_play`.
+    fn handoff(offense: &Roster, state: &GameState, big_play: i32) -> PlayResult {
+        let play = standard_play(
+            offense,
+            &roster("Away"),
+            offense_call(OffensivePlayType::IL, OffensiveBox::B1),
+            defense_call(DefensivePlay::PassDefense),
+        );
+        run(state, vec![card(1), card(2), pass_card(3, big_play)], play)
+            .unwrap()
+            .result
+    }
+
+    fn chance(result: &PlayResult) -> Option<&String> {
+        result
+            .mechanic
+            .iter()
+            .find(|m| m.starts_with("Big play on"))
+    }
+
+    #[test]
+    fn test_big_play_chance_uses_the_home_or_road_rating() {
+        let home = BIG_PLAY.chance_per_point * 2;
+        let road = BIG_PLAY.chance_per_point;
+        let on_the_road = GameState {
+            possession: GameTeams::Away,
+            ..state()
+        };
+        let neutral = GameState {
+            neutral_site: true,
+            ..state()
+        };
+        for (state, rating) in [(state(), home), (on_the_road, road), (neutral, road)] {
+            let result = handoff(&rated(), &state, 20);
+            assert_eq!(
+                chance(&result),
+                Some(&format!("Big play on {} or less", rating))
+            );
+            assert!(result.big_play_tried);
+            assert_eq!(result.result, 6);
+        }
+    }
+
+    #[test]
+    fn test_big_play_needs_a_rating_and_comes_once_a_drive() {
+        let unrated = handoff(&roster("Home"), &state(), 1);
+        assert!(chance(&unrated).is_none());
+        assert!(!unrated.big_play_tried);
+
+        let tried = GameState {
+            big_play_tried: true,
+            ..state()
+        };
+        assert!(chance(&handoff(&rated(), &tried, 1)).is_none());
+    }
+
+    #[test]
+    fn test_big_play_doubles_the_gain() {
+        let result = handoff(&rated(), &state(), BIG_PLAY.chance_per_point * 2);
+        assert!(said(&result.details, "It's a big play!"));
+        assert_eq!(result.result, 6 * BIG_PLAY.multiplier);
+    }
+
+    #[test]
+    fn test_basic_rules_refuse_advanced_strategies() {
+        let basic = GameState {
+            rules: RulesLevel::Basic,
+            ..state()
+        };
+        let refused = |off_call: StandardOffenseCall, def_call: StandardDefenseCall| {
+            let play = standard_play(&roster("Home"), &roster("Away"), off_call, def_call);
+            let err = run(&basic, vec![card(1)], play).unwrap_err();
+            assert_eq!(err.code(), "invalid_call");
+            assert!(err
+                .to_string()
+                .contains("only available with advanced rules"));
+        };
+        let straight = || defense_call(DefensivePlay::PassDefense);
+
+        refused(
+            StandardOffenseCall {
+                strategy: OffensiveStrategy::Draw,
+                ..offense_call(OffensivePlayType::IL, OffensiveBox::B1)
+            },
+            straight(),
+        );
+        refused(
+            StandardOffenseCall {
+                strategy: OffensiveStrategy::PlayAction,
+                ..offense_call(OffensivePlayType::SH, OffensiveBox::FL1)
+            },
+            straight(),
+        );
+        for strategy in [
+            DefensiveStrategy::DoubleCover,
+            DefensiveStrategy::DoubleCoverX2,
+            DefensiveStrategy::TripleCover,
+        ] {
+            refused(
+                offense_call(OffensivePlayType::SH, OffensiveBox::FL1),
+                StandardDefenseCall {
+                    strategy,
+                    ..straight()
+                },
+            );
+        }
+    }
+}
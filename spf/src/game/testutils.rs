@@ -0,0 +1,423 @@
+//! Shared fixtures for unit tests: a made-up squad with flat, predictable ratings, FAC cards
+//! that say nothing until a test fills in the column it reads, and helpers that run one play
+//! from them. Apart from `league_environment`, nothing here touches the data files, so every
+//! test is deterministic (see `docs/design/testing-strategy.md` §5).
+
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::{
+    engine::{
+        run_play, DefenseCall, DefenseIDLineup, KickoffDefenseCall, KickoffKind,
+        KickoffOffenseCall, OffenseCall, OffenseIDLineup, PlayImpl, PlayType,
+    },
+    environment::GameEnvironment,
+    error::GameError,
+    fac::{
+        FacCard, FacData, FacManager, PassTarget, RunBlocker, RunDirection, RunDirectionActual,
+        RunNum, ScreenResult,
+    },
+    lineup::{
+        DefensiveBox, KickoffIDDefenseLineup, KickoffIDOffenseLineup, OffensiveBox,
+        StandardIDDefenseLineup, StandardIDOffenseLineup,
+    },
+    players::{Player, Roster, TeamID},
+    standard_play::{
+        DefensivePlay, DefensiveStrategy, OffensivePlayType, OffensiveStrategy, PassResult,
+        StandardDefenseCall, StandardOffenseCall,
+    },
+    Game, GameOptions, GamePlayStatus, GameState, GameTeams, PlayAndState,
+};
+
+pub fn team(name: &str) -> TeamID {
+    TeamID {
+        name: name.into(),
+        year: "1983".into(),
+    }
+}
+
+/// A card with run number 1 and pass number 1, empty run results and every pass thrown to
+/// the called receiver. Tests set the columns the play reads with struct update.
+pub fn card(id: i32) -> FacData {
+    let empty = || {
+        RunDirection::Actual(RunDirectionActual {
+            offensive_boxes: vec![],
+            defensive_boxes: vec![],
+        })
+    };
+    FacData {
+        id,
+        run_num: RunNum { num: 1, ob: false },
+        pass_num: 1,
+        sl: empty(),
+        il: empty(),
+        ir: empty(),
+        sr: empty(),
+        er: "OK".to_string(),
+        sc: ScreenResult {
+            result: PassResult::Complete,
+            multiplier: 1.0,
+        },
+        sh: PassTarget::Orig,
+        qk: PassTarget::Orig,
+        lg: PassTarget::Orig,
+        z_result: String::new(),
+        solitaire: String::new(),
+    }
+}
+
+/// A card that only sets the run number.
+pub fn run_card(id: i32, num: i32) -> FacData {
+    FacData {
+        run_num: RunNum { num, ob: false },
+        ..card(id)
+    }
+}
+
+/// A card that only sets the pass number.
+pub fn pass_card(id: i32, num: i32) -> FacData {
+    FacData {
+        pass_num: num,
+        ..card(id)
+    }
+}
+
+/// Cards for a drive: a kickoff touchback on the first, then, dealt two to a handoff,
+/// gains that alternate 8 and 6 yards, so a deck dealt from the wrong spot shows up in the
+/// results.
+pub fn drive_cards() -> Vec<FacData> {
+    let il = |offensive_boxes, defensive_boxes| {
+        RunDirection::Actual(RunDirectionActual {
+            offensive_boxes,
+            defensive_boxes,
+        })
+    };
+    vec![
+        run_card(1, 11),
+        FacData {
+            il: il(
+                vec![
+                    RunBlocker::Box(OffensiveBox::LG),
+                    RunBlocker::Box(OffensiveBox::LT),
+                ],
+                vec![],
+            ),
+            ..card(2)
+        },
+        FacData {
+            il: il(vec![], vec![DefensiveBox::BoxF]),
+            ..card(3)
+        },
+        FacData {
+            il: il(vec![RunBlocker::Box(OffensiveBox::C)], vec![]),
+            ..card(4)
+        },
+    ]
+}
+
+/// A deck that deals `cards` in order, over and over.
+pub fn deck(cards: Vec<FacData>) -> FacManager {
+    FacManager::from_cards(cards.into_iter().map(FacCard::from).collect())
+}
+
+fn player(pos: &str, id: &str, team: &TeamID, ratings: Value) -> Player {
+    let mut p = json!({"team": team, "name": id, "id": id, "position": pos});
+    p.as_object_mut()
+        .unwrap()
+        .extend(ratings.as_object().unwrap().clone());
+    serde_json::from_value(json!({ pos: p })).unwrap()
+}
+
+fn twelve(row: Value) -> Value {
+    json!({ "stats": vec![row; 12] })
+}
+
+/// A full squad in card order (starters first). Every carrier gains 4 on a run and 5/10/20
+/// on a quick/short/long catch, passes complete on 1-30 and pick off on 48, linemen block 2
+/// and tackle -1, and the returner brings every kick back 20 yards.
+pub fn squad(name: &str) -> Vec<Player> {
+    let t = team(name);
+    let mut players = vec![];
+    for id in ["QB-1", "QB-2"] {
+        players.push(player(
+            "QB",
+            id,
+            &t,
+            json!({
+                "endurance": "A",
+                "quick": {"stats": {"Complete": "1-30", "Incomplete": "31-47", "Interception": "48-48"}},
+                "short": {"stats": {"Complete": "1-30", "Incomplete": "31-47", "Interception": "48-48"}},
+                "long": {"stats": {"Complete": "1-30", "Incomplete": "31-47", "Interception": "48-48"}},
+                "long_run": "M",
+                "pass_rush": {"stats": {"Sack": "1-12", "Runs": "13-24", "Complete": "25-36", "Incomplete": "37-48"}},
+                "endurance_rushing": 4,
+                "rushing": twelve(json!({"Val": 4})),
+            }),
+        ));
+    }
+    let rushing = twelve(json!({"stats": {"N": {"Val": 4}}}));
+    let pass_gain = twelve(json!({"stats": {"Q": {"Val": 5}, "S": {"Val": 10}, "L": {"Val": 20}}}));
+    for id in ["RB-1", "RB-2"] {
+        players.push(player(
+            "RB",
+            id,
+            &t,
+            json!({"rushing": rushing, "pass_gain": pass_gain, "lg": "M", "blocks": 1}),
+        ));
+    }
+    for id in ["WR-1", "WR-2", "WR-3"] {
+        players.push(player(
+            "WR",
+            id,
+            &t,
+            json!({"rushing": rushing, "pass_gain": pass_gain, "end": 4, "lg": " ", "blocks": -1}),
+        ));
+    }
+    players.push(player(
+        "TE",
+        "TE-1",
+        &t,
+        json!({"rushing": rushing, "pass_gain": pass_gain, "blocks": 1, "long_rush": "N"}),
+    ));
+    for n in 1..=5 {
+        players.push(player(
+            "OL",
+            &format!("OL-{}", n),
+            &t,
+            json!({"blocks": 2, "pass_block": 1}),
+        ));
+    }
+    for n in 1..=4 {
+        players.push(player(
+            "DL",
+            &format!("DL-{}", n),
+            &t,
+            json!({"tackles": -1, "pass_rush": 1}),
+        ));
+    }
+    for n in 1..=4 {
+        players.push(player(
+            "LB",
+            &format!("LB-{}", n),
+            &t,
+            json!({"tackles": -1, "pass_rush": 1, "pass_def": 0, "intercepts": "48-48"}),
+        ));
+    }
+    for n in 1..=4 {
+        players.push(player(
+            "DB",
+            &format!("DB-{}", n),
+            &t,
+            json!({"pass_def": 0, "pass_rush": 0, "intercepts": "48-48"}),
+        ));
+    }
+    players.push(player(
+        "K",
+        "K-1",
+        &t,
+        json!({
+            "field_goals": {"stats": {"18-25": "1-40", "26-35": "1-30", "36-45": "1-20", "46-50": "1-5"}},
+            "over_fifty": "49-49",
+            "extra_points": "1-46",
+            "longest_fg": 50,
+        }),
+    ));
+    let returns = twelve(json!({"yards": 20, "fumble": false, "asterisk": false}));
+    players.push(player(
+        "KR",
+        "KR-1",
+        &t,
+        json!({"returners": [
+            {"Actual": {"name": "KR-1", "return_stats": returns, "asterisk_val": 100}},
+            {"SameAs": 1},
+            {"SameAs": 1},
+            {"SameAs": 1},
+        ]}),
+    ));
+    players
+}
+
+pub fn roster(name: &str) -> Roster {
+    Roster::from_players(team(name), squad(name))
+}
+
+/// `squad` with `change` applied to each player, to give one of them other ratings.
+pub fn roster_with(name: &str, change: impl Fn(&mut Player)) -> Roster {
+    let mut players = squad(name);
+    players.iter_mut().for_each(change);
+    Roster::from_players(team(name), players)
+}
+
+/// Home has the ball, first and ten at midfield.
+pub fn state() -> GameState {
+    GameState {
+        possession: GameTeams::Home,
+        last_status: GamePlayStatus::Ongoing,
+        ..GameState::start_state()
+    }
+}
+
+pub fn offense_call(play_type: OffensivePlayType, target: OffensiveBox) -> StandardOffenseCall {
+    StandardOffenseCall {
+        play_type,
+        strategy: OffensiveStrategy::NoStrategy,
+        target,
+        blocking_back: None,
+        throw_away: false,
+    }
+}
+
+pub fn defense_call(defense_type: DefensivePlay) -> StandardDefenseCall {
+    StandardDefenseCall {
+        defense_type,
+        strategy: DefensiveStrategy::Straight,
+        key: None,
+        def_players: vec![],
+    }
+}
+
+pub fn standard_lineups(team: &Roster) -> (OffenseIDLineup, DefenseIDLineup) {
+    (
+        OffenseIDLineup::StandardIDOffenseLineup(StandardIDOffenseLineup::starters(team).unwrap()),
+        DefenseIDLineup::StandardIDDefenseLineup(StandardIDDefenseLineup::starters(team).unwrap()),
+    )
+}
+
+/// The kicker with the first `coverage` linebackers covering, and the team's returner.
+pub fn kickoff_lineups(coverage: usize) -> (OffenseIDLineup, DefenseIDLineup) {
+    (
+        OffenseIDLineup::KickoffIDOffenseLineup(KickoffIDOffenseLineup {
+            k: "K-1".into(),
+            coverage: (1..=coverage).map(|n| format!("LB-{}", n)).collect(),
+        }),
+        DefenseIDLineup::KickoffIDDefenseLineup(KickoffIDDefenseLineup { kr: "KR-1".into() }),
+    )
+}
+
+/// A kickoff of `kind`, `coverage` linebackers covering, to the receiving team's returner.
+pub fn kickoff_play(
+    kicking: &Roster,
+    receiving: &Roster,
+    coverage: usize,
+    kind: KickoffKind,
+    onside_expected: bool,
+) -> Box<dyn PlayImpl + Send> {
+    let (kicker, returner) = kickoff_lineups(coverage);
+    let mut play = PlayType::Kickoff.create_impl();
+    play.set_offense_lineup(&kicker, kicking).unwrap();
+    play.set_defense_lineup(&returner, receiving).unwrap();
+    play.set_offense_call(OffenseCall::KickoffOffenseCall(KickoffOffenseCall { kind }))
+        .unwrap();
+    play.set_defense_call(DefenseCall::KickoffDefenseCall(KickoffDefenseCall {
+        onside_expected,
+    }))
+    .unwrap();
+    play
+}
+
+/// A standard play with both teams' starters and the given calls.
+pub fn standard_play(
+    offense: &Roster,
+    defense: &Roster,
+    off_call: StandardOffenseCall,
+    def_call: StandardDefenseCall,
+) -> Box<dyn PlayImpl + Send> {
+    let mut play = PlayType::Standard.create_impl();
+    play.set_offense_lineup(&standard_lineups(offense).0, offense)
+        .unwrap();
+    play.set_defense_lineup(&standard_lineups(defense).1, defense)
+        .unwrap();
+    play.set_offense_call(OffenseCall::StandardOffenseCall(off_call))
+        .unwrap();
+    play.set_defense_call(DefenseCall::StandardDefenseCall(def_call))
+        .unwrap();
+    play
+}
+
+/// Run `play` from `state`, dealing `cards` in order.
+pub fn run(
+    state: &GameState,
+    cards: Vec<FacData>,
+    play: Box<dyn PlayImpl + Send>,
+) -> Result<PlayAndState, GameError> {
+    run_play(state, &mut deck(cards), &play)
+}
+
+/// Whether any line of `lines` (a result's details or mechanics) contains `text`.
+pub fn said(lines: &[String], text: &str) -> bool {
+    lines.iter().any(|l| l.contains(text))
+}
+
+/// A game between two fixture squads, "Home" and "Away", dealing `cards` in order.
+pub fn game(cards: Vec<FacData>, options: GameOptions) -> Game {
+    Game::build(roster("Home"), roster("Away"), deck(cards), options)
+}
+
+/// `game` with the deck seeded, so its log records `seed`. The cards still come in order.
+pub fn seeded_game(cards: Vec<FacData>, seed: u64, options: GameOptions) -> Game {
+    Game::build(
+        roster("Home"),
+        roster("Away"),
+        deck(cards).with_seed(seed),
+        options,
+    )
+}
+
+/// `drive_cards` with every solitaire column reading a plain run defense.
+pub fn solitaire_cards() -> Vec<FacData> {
+    drive_cards()
+        .into_iter()
+        .map(|card| FacData {
+            solitaire: "1.R(NK)/2.R(NK)/3.R(NK)/4.R(NK)/5.R(NK)".into(),
+            ..card
+        })
+        .collect()
+}
+
+/// Away kicks off to Home, whose starters then line up against Away's. With
+/// `run_card(_, 11)` on top of the deck the kick is a touchback.
+pub fn kick_off_to_home(game: &mut Game) {
+    let (kicker, returner) = kickoff_lineups(0);
+    game.set_offensive_lineup_from_ids(&kicker).unwrap();
+    game.set_defensive_lineup_from_ids(&returner).unwrap();
+    game.set_offense_call(OffenseCall::KickoffOffenseCall(KickoffOffenseCall {
+        kind: KickoffKind::Normal,
+    }))
+    .unwrap();
+    game.run_current_play().unwrap();
+    assert_eq!(game.state.possession, GameTeams::Home);
+
+    let (home, away) = (game.home.clone(), game.away.clone());
+    game.set_offensive_lineup_from_ids(&standard_lineups(&home).0)
+        .unwrap();
+    game.set_defensive_lineup_from_ids(&standard_lineups(&away).1)
+        .unwrap();
+}
+
+/// A handoff to B1 up the middle.
+pub fn handoff() -> OffenseCall {
+    OffenseCall::StandardOffenseCall(offense_call(OffensivePlayType::IL, OffensiveBox::B1))
+}
+
+/// Run `off_call` against a straight pass defense, with the lineups already set.
+pub fn run_standard(game: &mut Game, off_call: OffenseCall) -> PlayAndState {
+    game.set_offense_call(off_call).unwrap();
+    game.set_defense_call(DefenseCall::StandardDefenseCall(defense_call(
+        DefensivePlay::PassDefense,
+    )))
+    .unwrap();
+    game.run_current_play().unwrap()
+}
+
+/// The converted 1983 league and the FAC deck, for the few tests that play whole games on
+/// real cards. `None` (and a note on stderr) when that data isn't checked out, so the test
+/// can skip itself.
+pub fn league_environment() -> Option<GameEnvironment> {
+    let (data_dir, fac_path) = ("../data/1983", "../cards/fac_cards.csv");
+    if !Path::new(data_dir).exists() || !Path::new(fac_path).exists() {
+        eprintln!("skipping: {} not present", data_dir);
+        return None;
+    }
+    Some(GameEnvironment::load(data_dir, fac_path).expect("environment loads"))
+}
@@ -0,0 +1,9 @@
+//! Statis Pro Football game engine and HTTP server.
+//!
+//! The library exposes the [`game`] domain (engine, FAC deck, simulation) so tools such as
+//! `spf-cli` can drive games headlessly; the `spf` binary wraps it in the actix-web server
+//! from [`webendpoint`].
+
+pub mod game;
+pub mod webendpoint;
+pub mod webhooks;
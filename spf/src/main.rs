@@ -1,10 +1,7 @@
-mod game;
-mod webendpoint;
-
 use std::process::ExitCode;
 
-use crate::game::environment::GameEnvironment;
-use crate::webendpoint::runserver;
+use spf::game::environment::GameEnvironment;
+use spf::webendpoint::runserver;
 
 /// Directory holding the pre-generated persistent card data for the season the
 /// server runs. Produce it with:
@@ -14,6 +11,10 @@ const DATA_DIR: &str = "data/1983";
 /// FAC deck CSV, parsed at runtime (see `docs/design/data-pipeline.md`).
 const FAC_PATH: &str = "cards/fac_cards.csv";
 
+/// Where each game's append-only log is written (one `.jsonl` per game), so a game can be
+/// recovered with `POST /game/replay` after a restart.
+const LOG_DIR: &str = "games";
+
 fn main() -> ExitCode {
     let env = match GameEnvironment::load(DATA_DIR, FAC_PATH) {
         Ok(e) => e,
@@ -23,7 +24,7 @@ fn main() -> ExitCode {
         }
     };
 
-    match runserver(env) {
+    match runserver(env, LOG_DIR.to_string()) {
         Ok(_) => ExitCode::SUCCESS,
         Err(e) => {
             eprintln!("Server error: {}", e);
@@ -1,14 +1,18 @@
-use std::{str::FromStr, sync::Mutex};
+use std::{collections::VecDeque, fs, str::FromStr, sync::Mutex, time::Duration};
 
 use actix_cors::Cors;
 use actix_web::{
-    get, http::header, post, rt, web, App, HttpRequest, HttpResponse, HttpServer, Responder,
+    error::InternalError,
+    get,
+    http::{header, StatusCode},
+    patch, post, rt, web, App, HttpRequest, HttpResponse, HttpServer, Responder,
 };
 use actix_ws::Message;
-use futures_util::StreamExt;
-use serde::Deserialize;
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tokio::sync::broadcast::error::RecvError;
+use spf_core::persist;
+use tokio::sync::broadcast::{self, error::RecvError};
 use utoipa::{IntoParams, OpenApi, ToSchema};
 use utoipa_actix_web::{scope, AppExt};
 use utoipa_swagger_ui::SwaggerUi;
@@ -16,15 +20,89 @@ use utoipa_swagger_ui::SwaggerUi;
 use crate::game::{
     engine::{DefenseCall, DefenseIDLineup, OffenseCall, OffenseIDLineup, PlayResult, PlayType},
     environment::GameEnvironment,
-    events::GameEvent,
+    error::GameError,
+    events::{GameEvent, SequencedEvent},
+    gamelog::{self, LogEntry},
+    lineup::{
+        DepthChart, FormationLineup, LineupChanges, StandardIDDefenseLineup,
+        StandardIDOffenseLineup,
+    },
+    lineup_report::LineupReport,
     players::{Serializable_Roster, TeamID},
-    CreateGameError, Game, GameState, PlayAndState, PlayTypeInfo,
+    seats::{Seat, SeatAction, SeatTokens, SeatView},
+    standard_play::DefensiveStrategy,
+    CreateGameError, Game, GameOptions, GameState, GameTeams, PlayAndState, PlayTypeInfo,
 };
+use crate::webhooks::{self, RetryPolicy, Webhook, WebhookRequest};
 
 #[derive(Deserialize, ToSchema)]
 struct StartGameRequest {
     home: TeamID,
     away: TeamID,
+    /// Optional per-game settings (e.g. `"rules": "Basic"`); defaults to the advanced game.
+    #[serde(flatten)]
+    options: GameOptions,
+}
+
+/// The body of every error response. `code` is stable and what clients should match on;
+/// `message` is for people and may change.
+#[derive(Serialize, ToSchema)]
+struct ErrorResponse {
+    /// e.g. `no_game`, `not_allowed`, `illegal_formation`, `invalid_call`.
+    code: String,
+    message: String,
+}
+
+fn error_response(status: StatusCode, code: &str, message: impl ToString) -> HttpResponse {
+    HttpResponse::build(status).json(ErrorResponse {
+        code: code.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// A [`GameError`] as a response: 403 for a seat that may not act, 409 when the game isn't
+/// ready for the request, 404 for a play that doesn't exist, 422 for a lineup or call the
+/// rules refuse, and 500 when the engine itself fails.
+fn game_error(e: GameError) -> HttpResponse {
+    let status = match &e {
+        GameError::NotAllowed(_) => StatusCode::FORBIDDEN,
+        GameError::NoPlaySet
+        | GameError::NotReady(_)
+        | GameError::IllegalPlayType { .. }
+        | GameError::NoSuchPlay(None) => StatusCode::CONFLICT,
+        GameError::NoSuchPlay(Some(_)) => StatusCode::NOT_FOUND,
+        GameError::WrongPlayType(_) | GameError::Lineup(_) | GameError::InvalidCall(_) => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+        GameError::Engine(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error_response(status, e.code(), e)
+}
+
+fn no_game() -> HttpResponse {
+    error_response(StatusCode::CONFLICT, "no_game", "No game in progress")
+}
+
+fn game_in_progress() -> HttpResponse {
+    error_response(
+        StatusCode::CONFLICT,
+        "game_in_progress",
+        "A game is already in progress",
+    )
+}
+
+fn unknown_token() -> HttpResponse {
+    error_response(
+        StatusCode::UNAUTHORIZED,
+        "unauthorized",
+        "Missing or unknown seat token",
+    )
+}
+
+/// Answers a body or query string actix can't parse with the same JSON error body.
+fn bad_request(err: impl Into<actix_web::Error> + ToString) -> actix_web::Error {
+    let resp = error_response(StatusCode::BAD_REQUEST, "bad_request", err.to_string());
+    InternalError::from_response(err.into(), resp).into()
 }
 
 /// Locks the shared game state and binds `$game` to a `&mut Game`.
@@ -34,11 +112,92 @@ macro_rules! lock_game {
         let mut guard = $appstate.game.lock().unwrap();
         let $game = match guard.as_mut() {
             Some(g) => g,
-            None => return HttpResponse::Conflict().body("No game in progress"),
+            None => return no_game(),
         };
     };
 }
 
+/// Response to creating (or replaying) a game: its state plus the seat tokens to hand out.
+#[derive(Serialize, ToSchema)]
+struct StartGameResponse {
+    #[serde(flatten)]
+    state: GameState,
+    seats: SeatTokens,
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: String,
+}
+
+/// The team a coach's seat coaches, early-returning 403 Forbidden for anyone else.
+macro_rules! coached_team {
+    ($seat:expr) => {
+        match $seat.team() {
+            Some(team) => team,
+            None => return game_error(GameError::NotAllowed($seat)),
+        }
+    };
+}
+
+#[derive(Deserialize, IntoParams)]
+struct StrategyQuery {
+    /// The defensive strategy to check row sizes against; defaults to the pending call's.
+    strategy: Option<DefensiveStrategy>,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct EventsQuery {
+    /// The last event `seq` the client has; it receives everything after it.
+    since: Option<u64>,
+}
+
+/// How often an idle event stream sends an SSE comment, so proxies keep the connection open.
+const SSE_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// The seat a watching (streaming) client gets: its token's, a spectator's without one, or
+/// `None` for a token that isn't this game's.
+fn watcher_seat(req: &HttpRequest, game: &Game) -> Option<Seat> {
+    match request_token(req) {
+        None => Some(Seat::Spectator),
+        Some(t) => game.seat_for(&t),
+    }
+}
+
+/// The seat token from `Authorization: Bearer <token>`, or from a `?token=` query parameter
+/// for WebSocket clients, which can't set headers.
+fn request_token(req: &HttpRequest) -> Option<String> {
+    let header = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|t| t.trim().to_string());
+    header.or_else(|| {
+        web::Query::<TokenQuery>::from_query(req.query_string())
+            .ok()
+            .map(|q| q.into_inner().token)
+    })
+}
+
+/// Binds `$seat` to the requesting [`Seat`], early-returning 401 Unauthorized when the
+/// request carries none of this game's tokens. With an action, also early-returns 403
+/// Forbidden when that seat may not perform it now.
+macro_rules! require_seat {
+    ($req:expr, $game:expr, $seat:ident) => {
+        let $seat = match request_token(&$req).and_then(|t| $game.seat_for(&t)) {
+            Some(s) => s,
+            None => return unknown_token(),
+        };
+    };
+    ($req:expr, $game:expr, $seat:ident, $action:expr) => {
+        require_seat!($req, $game, $seat);
+        if let Err(e) = $game.authorize($seat, $action) {
+            return game_error(e);
+        }
+    };
+}
+
 #[derive(Deserialize, IntoParams)]
 struct PlayQueryParams {
     /// When `true`, each entry contains only `{result, new_state}` instead of the full play.
@@ -48,6 +207,12 @@ struct PlayQueryParams {
     count: Option<usize>,
 }
 
+#[derive(Deserialize, IntoParams)]
+struct UndoQueryParams {
+    /// Rewind to just before this play (a `play_counter` value). Defaults to the last play.
+    to: Option<u32>,
+}
+
 fn serialize_plays(plays: &[PlayAndState], result_only: bool) -> Result<String, serde_json::Error> {
     if result_only {
         // Return only PlayResult and GameState for each play
@@ -72,24 +237,29 @@ fn serialize_plays(plays: &[PlayAndState], result_only: bool) -> Result<String,
     request_body = OffenseIDLineup,
     responses(
         (status = 200, description = "Offensive lineup set"),
-        (status = 400, description = "Invalid lineup"),
-        (status = 409, description = "No game in progress"),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Invalid lineup (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or not ready for this yet", body = ErrorResponse),
     )
 )]
 #[post("/lineup")]
 async fn set_offensive_lineup(
+    req: HttpRequest,
     appstate: web::Data<AppState>,
     lineup: web::Json<OffenseIDLineup>,
 ) -> impl Responder {
     println!("{:?}", lineup); // Do something with the OffensiveLineup struct
     lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Offense);
     let lineup_obj = lineup.into_inner();
 
     println!("{:?}", lineup_obj); // Do something with the OffensiveLineup struct
 
     match game.set_offensive_lineup_from_ids(&lineup_obj) {
         Ok(_) => HttpResponse::Ok().body("Offensive lineup set."),
-        Err(msg) => HttpResponse::BadRequest().body(msg),
+        Err(e) => game_error(e),
     }
 }
 
@@ -97,20 +267,22 @@ async fn set_offensive_lineup(
     tag = "offense",
     responses(
         (status = 200, description = "Current offensive lineup", body = OffenseIDLineup),
-        (status = 409, description = "No game in progress"),
-        (status = 500, description = "Serialization error"),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+        (status = 500, description = "Serialization error", body = ErrorResponse),
     )
 )]
 #[get("/lineup")]
-async fn get_offensive_lineup(appstate: web::Data<AppState>) -> impl Responder {
+async fn get_offensive_lineup(req: HttpRequest, appstate: web::Data<AppState>) -> impl Responder {
     println!("get_offensive_lineup called");
 
     lock_game!(appstate, game);
-    let lineup = game.get_offensive_lineup_ids();
+    require_seat!(req, game, seat);
+    let lineup = game.get_offensive_lineup_ids(seat);
     let res = serde_json::to_string(&lineup);
 
     return match res {
-        Err(msg) => HttpResponse::InternalServerError().body(msg.to_string()),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, "internal", e),
         Ok(v) => HttpResponse::Ok().content_type("application/json").body(v),
     };
 }
@@ -119,19 +291,21 @@ async fn get_offensive_lineup(appstate: web::Data<AppState>) -> impl Responder {
     tag = "defense",
     responses(
         (status = 200, description = "Current defensive lineup", body = DefenseIDLineup),
-        (status = 409, description = "No game in progress"),
-        (status = 500, description = "Serialization error"),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+        (status = 500, description = "Serialization error", body = ErrorResponse),
     )
 )]
 #[get("/lineup")]
-async fn get_defensive_lineup(appstate: web::Data<AppState>) -> impl Responder {
+async fn get_defensive_lineup(req: HttpRequest, appstate: web::Data<AppState>) -> impl Responder {
     println!("get_defensive_lineup called");
     lock_game!(appstate, game);
-    let lineup = game.get_defensive_lineup_ids();
+    require_seat!(req, game, seat);
+    let lineup = game.get_defensive_lineup_ids(seat);
     let res = serde_json::to_string(&lineup);
 
     return match res {
-        Err(msg) => HttpResponse::InternalServerError().body(msg.to_string()),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, "internal", e),
         Ok(v) => HttpResponse::Ok().content_type("application/json").body(v),
     };
 }
@@ -141,36 +315,290 @@ async fn get_defensive_lineup(appstate: web::Data<AppState>) -> impl Responder {
     request_body = DefenseIDLineup,
     responses(
         (status = 200, description = "Defensive lineup set"),
-        (status = 400, description = "Invalid lineup"),
-        (status = 409, description = "No game in progress"),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Invalid lineup (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or not ready for this yet", body = ErrorResponse),
     )
 )]
 #[post("/lineup")]
 async fn set_defensive_lineup(
+    req: HttpRequest,
     appstate: web::Data<AppState>,
     lineup: web::Json<DefenseIDLineup>,
 ) -> impl Responder {
     println!("{:?}", lineup); // Do something with the DefensiveLineup struct
     lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Defense);
     let lineup_obj = lineup.into_inner();
 
     match game.set_defensive_lineup_from_ids(&lineup_obj) {
         Ok(_) => HttpResponse::Ok().body("Defensive lineup set."),
-        Err(msg) => HttpResponse::BadRequest().body(msg),
+        Err(e) => game_error(e),
+    }
+}
+
+#[utoipa::path(
+    tag = "offense",
+    request_body = OffenseIDLineup,
+    responses(
+        (status = 200, description = "Every problem with the lineup; nothing is set", body = LineupReport),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or no play type set", body = ErrorResponse),
+    )
+)]
+#[post("/lineup/validate")]
+async fn validate_offensive_lineup(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    lineup: web::Json<OffenseIDLineup>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Offense);
+    match game.check_offensive_lineup(&lineup) {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(e) => game_error(e),
+    }
+}
+
+#[utoipa::path(
+    tag = "defense",
+    request_body = DefenseIDLineup,
+    params(StrategyQuery),
+    responses(
+        (status = 200, description = "Every problem with the lineup and the formations the strategy allows; nothing is set", body = LineupReport),
+        (status = 400, description = "Malformed body or unknown strategy", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or no play type set", body = ErrorResponse),
+    )
+)]
+#[post("/lineup/validate")]
+async fn validate_defensive_lineup(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    lineup: web::Json<DefenseIDLineup>,
+    query: web::Query<StrategyQuery>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Defense);
+    match game.check_defensive_lineup(&lineup, query.into_inner().strategy) {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(e) => game_error(e),
     }
 }
 
+#[utoipa::path(
+    tag = "offense",
+    request_body = FormationLineup,
+    responses(
+        (status = 200, description = "Offensive lineup set from the formation"),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Unknown formation, bad substitution or invalid lineup (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or not ready for this yet", body = ErrorResponse),
+    )
+)]
+#[post("/lineup/formation")]
+async fn set_offensive_formation(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    pick: web::Json<FormationLineup>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Offense);
+    match game.set_offensive_lineup_from_formation(&pick) {
+        Ok(_) => HttpResponse::Ok().body("Offensive lineup set."),
+        Err(e) => game_error(e),
+    }
+}
+
+#[utoipa::path(
+    tag = "defense",
+    request_body = FormationLineup,
+    responses(
+        (status = 200, description = "Defensive lineup set from the formation"),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Unknown formation, bad substitution or invalid lineup (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or not ready for this yet", body = ErrorResponse),
+    )
+)]
+#[post("/lineup/formation")]
+async fn set_defensive_formation(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    pick: web::Json<FormationLineup>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Defense);
+    match game.set_defensive_lineup_from_formation(&pick) {
+        Ok(_) => HttpResponse::Ok().body("Defensive lineup set."),
+        Err(e) => game_error(e),
+    }
+}
+
+#[utoipa::path(
+    tag = "offense",
+    request_body = LineupChanges,
+    responses(
+        (status = 200, description = "Offensive lineup changed"),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Bad substitution or invalid lineup (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or no lineup to change", body = ErrorResponse),
+    )
+)]
+#[patch("/lineup")]
+async fn change_offensive_lineup(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    changes: web::Json<LineupChanges>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Offense);
+    match game.change_offensive_lineup(&changes) {
+        Ok(_) => HttpResponse::Ok().body("Offensive lineup set."),
+        Err(e) => game_error(e),
+    }
+}
+
+#[utoipa::path(
+    tag = "defense",
+    request_body = LineupChanges,
+    responses(
+        (status = 200, description = "Defensive lineup changed"),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Bad substitution or invalid lineup (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or no lineup to change", body = ErrorResponse),
+    )
+)]
+#[patch("/lineup")]
+async fn change_defensive_lineup(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    changes: web::Json<LineupChanges>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Defense);
+    match game.change_defensive_lineup(&changes) {
+        Ok(_) => HttpResponse::Ok().body("Defensive lineup set."),
+        Err(e) => game_error(e),
+    }
+}
+
+/// Write the depth chart a formation was just saved to back to the team's data file.
+fn persist_depth_chart(appstate: &AppState, game: &Game, team: GameTeams) -> HttpResponse {
+    let chart = game.depth_chart(team);
+    match appstate
+        .env
+        .save_depth_chart(game.team_roster(team).get_team_name(), chart)
+    {
+        Ok(_) => HttpResponse::Ok().json(chart),
+        Err(msg) => error_response(StatusCode::INTERNAL_SERVER_ERROR, "save_failed", msg),
+    }
+}
+
+#[utoipa::path(
+    tag = "game",
+    responses(
+        (status = 200, description = "The requesting coach's team's formations", body = DepthChart),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "Spectators have no depth chart", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+    )
+)]
+#[get("/formations")]
+async fn get_formations(req: HttpRequest, appstate: web::Data<AppState>) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat);
+    let team = coached_team!(seat);
+    HttpResponse::Ok().json(game.depth_chart(team))
+}
+
+#[utoipa::path(
+    tag = "offense",
+    params(("name" = String, Path, description = "Formation name, e.g. I-form")),
+    request_body = StandardIDOffenseLineup,
+    responses(
+        (status = 200, description = "Saved; the team's updated depth chart", body = DepthChart),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Invalid lineup (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "Spectators have no depth chart", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+        (status = 500, description = "Could not write the team file", body = ErrorResponse),
+    )
+)]
+#[post("/formations/{name}")]
+async fn save_offense_formation(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    name: web::Path<String>,
+    lineup: web::Json<StandardIDOffenseLineup>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat);
+    let team = coached_team!(seat);
+    if let Err(e) = game.save_offense_formation(team, name.into_inner(), lineup.into_inner()) {
+        return game_error(e);
+    }
+    persist_depth_chart(&appstate, game, team)
+}
+
+#[utoipa::path(
+    tag = "defense",
+    params(("name" = String, Path, description = "Formation name, e.g. nickel")),
+    request_body = StandardIDDefenseLineup,
+    responses(
+        (status = 200, description = "Saved; the team's updated depth chart", body = DepthChart),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Invalid lineup (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "Spectators have no depth chart", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+        (status = 500, description = "Could not write the team file", body = ErrorResponse),
+    )
+)]
+#[post("/formations/{name}")]
+async fn save_defense_formation(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    name: web::Path<String>,
+    lineup: web::Json<StandardIDDefenseLineup>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat);
+    let team = coached_team!(seat);
+    if let Err(e) = game.save_defense_formation(team, name.into_inner(), lineup.into_inner()) {
+        return game_error(e);
+    }
+    persist_depth_chart(&appstate, game, team)
+}
+
 #[utoipa::path(
     tag = "offense",
     request_body = OffenseCall,
     responses(
         (status = 200, description = "Offense play set"),
-        (status = 400, description = "Invalid call"),
-        (status = 409, description = "No game in progress"),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Invalid call (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or not ready for this yet", body = ErrorResponse),
     )
 )]
 #[post("/call")]
 async fn set_offense_call(
+    req: HttpRequest,
     appstate: web::Data<AppState>,
     data: web::Json<OffenseCall>,
 ) -> impl Responder {
@@ -179,34 +607,90 @@ async fn set_offense_call(
     let call = data.into_inner();
     println!("Offense Play:  {:?}", call); // Do something with the plays
     lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Offense);
 
     match game.set_offense_call(call) {
         Ok(_) => HttpResponse::Ok().body("Offense play set."),
-        Err(msg) => HttpResponse::BadRequest().body(msg),
+        Err(e) => game_error(e),
     }
 }
 
+#[utoipa::path(
+    tag = "offense",
+    responses(
+        (
+            status = 200,
+            description = "`{set, call}` for the upcoming play; `call` is null until set, or \
+                           while the game hides calls",
+            body = Object
+        ),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+    )
+)]
+#[get("/call")]
+async fn get_offense_call(req: HttpRequest, appstate: web::Data<AppState>) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat);
+    let json_data = serde_json::to_string(&game.get_offense_call(seat))
+        .expect("Error while serializing call to JSON.");
+
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json_data)
+}
+
+#[utoipa::path(
+    tag = "defense",
+    responses(
+        (
+            status = 200,
+            description = "`{set, call}` for the upcoming play; `call` is null until set, or \
+                           while the game hides calls",
+            body = Object
+        ),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+    )
+)]
+#[get("/call")]
+async fn get_defense_call(req: HttpRequest, appstate: web::Data<AppState>) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat);
+    let json_data = serde_json::to_string(&game.get_defense_call(seat))
+        .expect("Error while serializing call to JSON.");
+
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json_data)
+}
+
 #[utoipa::path(
     tag = "defense",
     request_body = DefenseCall,
     responses(
         (status = 200, description = "Defense play set"),
-        (status = 400, description = "Invalid call"),
-        (status = 409, description = "No game in progress"),
+        (status = 400, description = "Malformed body", body = ErrorResponse),
+        (status = 422, description = "Invalid call (see `code`)", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or not ready for this yet", body = ErrorResponse),
     )
 )]
 #[post("/call")]
 async fn set_defense_call(
+    req: HttpRequest,
     appstate: web::Data<AppState>,
     data: web::Json<DefenseCall>,
 ) -> impl Responder {
     let call = data.into_inner();
     println!("Defense Play:  {:?}", call); // Do something with the plays
     lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Defense);
 
     match game.set_defense_call(call) {
         Ok(_) => HttpResponse::Ok().body("Defense play set."),
-        Err(msg) => HttpResponse::BadRequest().body(msg),
+        Err(e) => game_error(e),
     }
 }
 
@@ -214,14 +698,17 @@ async fn set_defense_call(
     tag = "game",
     responses(
         (status = 200, description = "Result of the executed play", body = PlayResult),
-        (status = 400, description = "Play could not be run"),
-        (status = 409, description = "No game in progress"),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or not ready for this yet", body = ErrorResponse),
+        (status = 500, description = "The engine could not run the play", body = ErrorResponse),
     )
 )]
 #[post("/play")]
-async fn run_play(appstate: web::Data<AppState>) -> impl Responder {
+async fn run_play(req: HttpRequest, appstate: web::Data<AppState>) -> impl Responder {
     println!("Running Play..."); // Do something with the plays
     lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Coach);
 
     match game.run_current_play() {
         Ok(res) => {
@@ -231,7 +718,40 @@ async fn run_play(appstate: web::Data<AppState>) -> impl Responder {
                 .content_type("application/json")
                 .body(json_data)
         }
-        Err(msg) => HttpResponse::BadRequest().body(msg),
+        Err(e) => game_error(e),
+    }
+}
+
+#[utoipa::path(
+    tag = "game",
+    params(UndoQueryParams),
+    responses(
+        (status = 200, description = "Play(s) taken back; returns the restored game state", body = GameState),
+        (status = 400, description = "`to` is not a number", body = ErrorResponse),
+        (status = 404, description = "No such play to rewind to", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or no plays to undo", body = ErrorResponse),
+    )
+)]
+#[post("/undo")]
+async fn undo_play(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    query: web::Query<UndoQueryParams>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Coach);
+
+    match game.undo(query.to) {
+        Ok(state) => {
+            let json_data =
+                serde_json::to_string(&state).expect("Error while serializing State to JSON.");
+            HttpResponse::Ok()
+                .content_type("application/json")
+                .body(json_data)
+        }
+        Err(e) => game_error(e),
     }
 }
 
@@ -240,8 +760,8 @@ async fn run_play(appstate: web::Data<AppState>) -> impl Responder {
     params(("team" = String, Path, description = "Team selector: home | away")),
     responses(
         (status = 200, description = "Roster for the selected team", body = Serializable_Roster),
-        (status = 404, description = "Unknown team selector"),
-        (status = 409, description = "No game in progress"),
+        (status = 404, description = "Unknown team selector", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
     )
 )]
 #[get("/players/{team}")]
@@ -255,7 +775,13 @@ async fn get_team_players(
     let team_rost = match team_path.as_str() {
         "home" => &game.home,
         "away" => &game.away,
-        _ => return HttpResponse::NotFound().finish(),
+        _ => {
+            return error_response(
+                StatusCode::NOT_FOUND,
+                "unknown_team",
+                format!("Unknown team selector: {}", team_path),
+            )
+        }
     };
 
     let srost = Serializable_Roster::from_roster(team_rost);
@@ -270,7 +796,7 @@ async fn get_team_players(
     tag = "game",
     responses(
         (status = 200, description = "Current game state", body = GameState),
-        (status = 409, description = "No game in progress"),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
     )
 )]
 #[get("/state")]
@@ -294,7 +820,7 @@ async fn get_game_state(appstate: web::Data<AppState>) -> impl Responder {
     tag = "game",
     responses(
         (status = 200, description = "Allowed and currently-selected next play types", body = PlayTypeInfo),
-        (status = 409, description = "No game in progress"),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
     )
 )]
 #[get("/nexttype")]
@@ -324,27 +850,37 @@ async fn get_next_play_types(appstate: web::Data<AppState>) -> impl Responder {
     ),
     responses(
         (status = 200, description = "Next play type set"),
-        (status = 400, description = "Unknown or illegal play type"),
-        (status = 409, description = "No game in progress"),
+        (status = 400, description = "Unknown play type", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress, or that play type can't come next", body = ErrorResponse),
     )
 )]
 #[post("/nexttype")]
-async fn set_next_play_type(appstate: web::Data<AppState>, data: String) -> impl Responder {
+async fn set_next_play_type(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    data: String,
+) -> impl Responder {
     println!("Set Next Play Called");
 
     println!("Play Type is {}", data);
-    let v = PlayType::from_str(&data);
-    if v.is_err() {
-        return HttpResponse::BadRequest().body("Unknown Type");
-    }
+    let Ok(play_type) = PlayType::from_str(&data) else {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "unknown_play_type",
+            format!("Unknown Type: {}", data),
+        );
+    };
 
     lock_game!(appstate, game);
-    let res = game.set_next_play_type(v.unwrap());
+    require_seat!(req, game, seat, SeatAction::Offense);
+    let res = game.set_next_play_type(play_type);
     match res {
         Ok(_) => HttpResponse::Ok()
             .content_type("application/json")
             .body("Set"),
-        Err(msg) => HttpResponse::BadRequest().body(msg),
+        Err(e) => game_error(e),
     }
 }
 
@@ -357,23 +893,30 @@ async fn set_next_play_type(appstate: web::Data<AppState>, data: String) -> impl
     ),
     responses(
         (status = 200, description = "Game saved"),
-        (status = 400, description = "Could not save game"),
-        (status = 409, description = "No game in progress"),
+        (status = 400, description = "Could not save game", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "This seat can't do that now", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
     )
 )]
 #[post("/save")]
-async fn save_game(appstate: web::Data<AppState>, data: String) -> impl Responder {
+async fn save_game(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    data: String,
+) -> impl Responder {
     println!("Save Game Called");
 
     println!("File is {}", data);
 
     lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Coach);
     let res = game.serialize_struct(data);
     match res {
         Ok(_) => HttpResponse::Ok()
             .content_type("application/json")
             .body("Set"),
-        Err(msg) => HttpResponse::BadRequest().body(msg.to_string()),
+        Err(e) => error_response(StatusCode::BAD_REQUEST, "save_failed", e),
     }
 }
 
@@ -382,7 +925,8 @@ async fn save_game(appstate: web::Data<AppState>, data: String) -> impl Responde
     params(("id" = String, Path, description = "Player ID, e.g. QB-1234")),
     responses(
         (status = 200, description = "Player record; shape varies by position", body = Object),
-        (status = 409, description = "No game in progress"),
+        (status = 404, description = "No such player in either roster", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
     )
 )]
 #[get("/getplayer/{id}")]
@@ -402,13 +946,15 @@ async fn get_player(path: web::Path<String>, appstate: web::Data<AppState>) -> i
 
     // let rec = league.get_player(&path_param);
 
-    let json_data = match rec {
-        Some(player) => player.get_json(),
-        None => json!({"err": "No such player"}),
+    let Some(player) = rec else {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            "unknown_player",
+            format!("No such player: {}", path_param),
+        );
     };
-
     let json_str =
-        serde_json::to_string(&json_data).unwrap_or("{\"err\": \"No such player\"}".to_string());
+        serde_json::to_string(&player.get_json()).expect("Error while serializing player to JSON.");
 
     HttpResponse::Ok()
         .content_type("application/json")
@@ -425,7 +971,7 @@ async fn get_player(path: web::Path<String>, appstate: web::Data<AppState>) -> i
                            `{result, new_state}`; `?count=N` limits to the last N plays.",
             body = Vec<PlayAndState>
         ),
-        (status = 409, description = "No game in progress"),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
     )
 )]
 #[get("/plays")]
@@ -460,14 +1006,110 @@ async fn get_all_plays(
         .body(json_data)
 }
 
+#[utoipa::path(
+    tag = "game",
+    responses(
+        (status = 200, description = "Every command applied to the game so far, in order, without the other side's pending lineup and call or, until the game is over, the deck seed", body = Vec<Object>),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "Spectators can't read the log", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+    )
+)]
+#[get("/log")]
+async fn get_game_log(req: HttpRequest, appstate: web::Data<AppState>) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Coach);
+    let json_data =
+        serde_json::to_string(&game.log_for(seat)).expect("Error while serializing log to JSON.");
+
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json_data)
+}
+
+#[utoipa::path(
+    tag = "game",
+    request_body(
+        content = String,
+        content_type = "text/plain",
+        description = "Name of a game log (`.jsonl`) in the server's log directory to rebuild the game from"
+    ),
+    responses(
+        (status = 200, description = "Game rebuilt; returns its current state and new seat tokens", body = StartGameResponse),
+        (status = 400, description = "Log could not be read or replayed", body = ErrorResponse),
+        (status = 409, description = "A game is already in progress", body = ErrorResponse),
+    )
+)]
+#[post("/replay")]
+async fn replay_game(appstate: web::Data<AppState>, data: String) -> impl Responder {
+    let mut guard = appstate.game.lock().unwrap();
+    if guard.is_some() {
+        return game_in_progress();
+    }
+
+    let mut game = match gamelog::log_path(&appstate.log_dir, &data)
+        .and_then(|path| gamelog::read_log(&path))
+        .and_then(|log| Game::replay(&appstate.env, &log))
+    {
+        Ok(g) => g,
+        Err(msg) => return error_response(StatusCode::BAD_REQUEST, "bad_log", msg),
+    };
+    // The recovered game gets a log of its own, so it stays recoverable without touching
+    // the one it was rebuilt from.
+    let stem = data.trim().trim_end_matches(".jsonl");
+    if let Err(msg) = game.persist_log(&format!("{}/{}-replayed.jsonl", appstate.log_dir, stem)) {
+        eprintln!("Not persisting replayed game log: {}", msg);
+    }
+    let response = StartGameResponse {
+        state: game.state,
+        seats: game.seat_tokens().clone(),
+    };
+    let json_data =
+        serde_json::to_string(&response).expect("Error while serializing State to JSON.");
+    *guard = Some(game);
+
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json_data)
+}
+
+/// Start writing a new game's log under `log_dir`, named for the teams and the deck seed.
+/// A failure only costs crash recovery, so it is reported rather than failing the request.
+fn persist_new_game_log(game: &mut Game, log_dir: &str) {
+    let name = match game.log().first() {
+        Some(LogEntry::Created {
+            home, away, seed, ..
+        }) => format!(
+            "{}-at-{}-{}.jsonl",
+            persist::sanitize_file_stem(&away.team.name),
+            persist::sanitize_file_stem(&home.team.name),
+            seed.unwrap_or_default()
+        ),
+        _ => return,
+    };
+    let result = fs::create_dir_all(log_dir)
+        .map_err(|e| format!("{}: {}", log_dir, e))
+        .and_then(|_| game.persist_log(&format!("{}/{}", log_dir, name)));
+    if let Err(msg) = result {
+        eprintln!("Not persisting game log: {}", msg);
+    }
+}
+
 struct AppState {
     env: GameEnvironment,
     game: Mutex<Option<Game>>,
+    /// Directory new games' logs are written to.
+    log_dir: String,
+    /// The current game's webhooks, each delivered by its own task.
+    webhooks: Mutex<Vec<Webhook>>,
 }
 
-/// Read-only WebSocket endpoint (`GET /game/ws`). On connect the client immediately
-/// receives the current game state (as a `GameStarted`-shaped `GameEvent`), then every
-/// subsequent `GameEvent` as a JSON text frame. Client commands stay on REST. Returns
+/// WebSocket endpoint (`GET /game/ws[?token=<seat token>][&since=<seq>]`). On connect the
+/// client receives the events after `since`, or else the current game state (as a
+/// `GameStarted`-shaped event), then every subsequent `SequencedEvent` as a JSON text frame,
+/// redacted for the client's seat
+/// (spectator without a token). Text frames from the client are `GameCommand`s run as that
+/// seat, each answered with a `CommandAck` (see `game/commands.rs`). Returns
 /// `409 Conflict` when no game is in progress. See `docs/design/ws-events-architecture.md`.
 ///
 /// Registered via `App::route` (not `#[get]`/utoipa `service`) because a WebSocket upgrade
@@ -481,22 +1123,31 @@ async fn game_ws(
 ) -> Result<HttpResponse, actix_web::Error> {
     // Briefly lock: read the snapshot and mint a receiver, then release the guard before
     // any async WS work (the Mutex guard must not be held across await points).
-    let (snapshot, mut rx) = {
+    let since = web::Query::<EventsQuery>::from_query(req.query_string())
+        .map(|q| q.since)
+        .unwrap_or(None);
+    let (catch_up, mut rx, seat, view) = {
         let mut guard = appstate.game.lock().unwrap();
         let game = match guard.as_mut() {
             Some(g) => g,
-            None => return Ok(HttpResponse::Conflict().body("No game in progress")),
+            None => return Ok(no_game()),
+        };
+        // No token watches as a spectator; a wrong one is refused.
+        let Some(seat) = watcher_seat(&req, game) else {
+            return Ok(unknown_token());
         };
-        (game.state, game.subscribe())
+        // Both under the same lock, so nothing falls between the catch-up and the stream.
+        (game.resync(since), game.subscribe(), seat, game.view(seat))
     };
 
     let (res, mut session, mut msg_stream) = actix_ws::handle(&req, body)?;
 
     rt::spawn(async move {
-        // Snapshot-then-stream: send the current state first.
-        let snapshot_ev = GameEvent::GameStarted { state: snapshot };
-        if let Ok(txt) = serde_json::to_string(&snapshot_ev) {
-            if session.text(txt).await.is_err() {
+        // Catch-up-then-stream: the missed events (or a snapshot) first.
+        let mut last_seq = 0;
+        for ev in catch_up {
+            last_seq = ev.seq;
+            if !send_event(&mut session, &view, ev).await {
                 return; // client already gone
             }
         }
@@ -505,15 +1156,29 @@ async fn game_ws(
         loop {
             tokio::select! {
                 event = rx.recv() => match event {
+                    Ok(ev) if ev.seq <= last_seq => {} // already sent during catch-up
                     Ok(ev) => {
-                        if let Ok(txt) = serde_json::to_string(&ev) {
-                            if session.text(txt).await.is_err() {
-                                break; // client disconnected mid-send
-                            }
+                        last_seq = ev.seq;
+                        if !send_event(&mut session, &view, ev).await {
+                            break; // client disconnected mid-send
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => {
+                        // Refill what the channel dropped from the game's history.
+                        let missed = match appstate.game.lock().unwrap().as_ref() {
+                            Some(game) => game.resync(Some(last_seq)),
+                            None => break,
+                        };
+                        let mut sent = true;
+                        for ev in missed {
+                            last_seq = ev.seq;
+                            sent = sent && send_event(&mut session, &view, ev).await;
+                        }
+                        if !sent {
+                            break;
                         }
                     }
-                    Err(RecvError::Lagged(_)) => continue, // skip & resync
-                    Err(RecvError::Closed) => break,       // game dropped
+                    Err(RecvError::Closed) => break, // game dropped
                 },
                 msg = msg_stream.next() => match msg {
                     Some(Ok(Message::Ping(bytes))) => {
@@ -521,8 +1186,20 @@ async fn game_ws(
                             break;
                         }
                     }
+                    Some(Ok(Message::Text(text))) => {
+                        // Hold the lock only for the command itself, never across the send.
+                        let ack = match appstate.game.lock().unwrap().as_mut() {
+                            Some(game) => game.handle_command_frame(seat, &text),
+                            None => break, // game gone
+                        };
+                        if let Ok(txt) = serde_json::to_string(&ack) {
+                            if session.text(txt).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
                     Some(Ok(Message::Close(_))) | None => break, // client closed / stream ended
-                    Some(Ok(_)) => {} // read-only: ignore Text/Binary/etc.
+                    Some(Ok(_)) => {} // ignore Binary/Pong/etc.
                     Some(Err(_)) => break, // protocol error
                 },
             }
@@ -534,13 +1211,178 @@ async fn game_ws(
     Ok(res)
 }
 
+/// Send one event, redacted for the connection's seat. `false` once the client is gone.
+async fn send_event(session: &mut actix_ws::Session, view: &SeatView, ev: SequencedEvent) -> bool {
+    match serde_json::to_string(&view.redact(ev)) {
+        Ok(txt) => session.text(txt).await.is_ok(),
+        Err(_) => true,
+    }
+}
+
+/// Server-Sent Events endpoint (`GET /game/events`): the same redacted `SequencedEvent`
+/// stream as [`game_ws`], catch-up (or snapshot) first, for clients that can't do
+/// WebSockets. Read-only; commands still go through REST. Each event is one `data:` line
+/// with its `seq` as the SSE `id`, so a browser `EventSource` resumes via `Last-Event-ID`.
+#[utoipa::path(
+    tag = "game",
+    params(EventsQuery),
+    responses(
+        (status = 200, description = "`text/event-stream` of `SequencedEvent` JSON; pass the seat token as `?token=` or a bearer header (spectator without one)", content_type = "text/event-stream", body = SequencedEvent),
+        (status = 401, description = "Unknown seat token", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+    )
+)]
+#[get("/events")]
+async fn game_events(
+    req: HttpRequest,
+    query: web::Query<EventsQuery>,
+    appstate: web::Data<AppState>,
+) -> impl Responder {
+    // A reconnecting EventSource sends the last id it saw instead of our query parameter.
+    let since = query.since.or_else(|| {
+        req.headers()
+            .get("Last-Event-ID")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+    });
+    let (catch_up, rx, view) = {
+        lock_game!(appstate, game);
+        let Some(seat) = watcher_seat(&req, game) else {
+            return unknown_token();
+        };
+        (game.resync(since), game.subscribe(), game.view(seat))
+    };
+
+    let stream = EventStream {
+        appstate: appstate.clone(),
+        rx,
+        view,
+        pending: catch_up.into(),
+        last_seq: 0,
+        keep_alive: rt::time::interval_at(
+            rt::time::Instant::now() + SSE_KEEP_ALIVE,
+            SSE_KEEP_ALIVE,
+        ),
+    };
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(stream::unfold(stream, |mut s| async move {
+            let frame = s.next_frame().await?;
+            Some((Ok::<_, actix_web::Error>(frame), s))
+        }))
+}
+
+/// One SSE connection's state, mirroring the pump in [`game_ws`].
+struct EventStream {
+    appstate: web::Data<AppState>,
+    rx: broadcast::Receiver<SequencedEvent>,
+    view: SeatView,
+    /// Events to send before reading the channel again (catch-up or a lag refill).
+    pending: VecDeque<SequencedEvent>,
+    last_seq: u64,
+    keep_alive: rt::time::Interval,
+}
+
+impl EventStream {
+    /// The next chunk of the response body; `None` ends the stream.
+    async fn next_frame(&mut self) -> Option<web::Bytes> {
+        loop {
+            if let Some(ev) = self.pending.pop_front() {
+                self.last_seq = ev.seq;
+                let seq = ev.seq;
+                let Ok(data) = serde_json::to_string(&self.view.redact(ev)) else {
+                    continue;
+                };
+                return Some(web::Bytes::from(format!("id: {}\ndata: {}\n\n", seq, data)));
+            }
+            tokio::select! {
+                event = self.rx.recv() => match event {
+                    Ok(ev) if ev.seq <= self.last_seq => {} // already sent during catch-up
+                    Ok(ev) => self.pending.push_back(ev),
+                    Err(RecvError::Lagged(_)) => {
+                        let missed = self.appstate.game.lock().unwrap().as_ref()?.resync(Some(self.last_seq));
+                        self.pending.extend(missed);
+                    }
+                    Err(RecvError::Closed) => return None, // game dropped
+                },
+                _ = self.keep_alive.tick() => {
+                    return Some(web::Bytes::from_static(b": keep-alive\n\n"));
+                }
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "game",
+    request_body = WebhookRequest,
+    responses(
+        (status = 200, description = "Webhook registered; the game's events now go to its URL", body = Webhook),
+        (status = 400, description = "Bad URL or event filter", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "Spectators can't register webhooks", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+    )
+)]
+#[post("/webhooks")]
+async fn add_webhook(
+    req: HttpRequest,
+    appstate: web::Data<AppState>,
+    data: web::Json<WebhookRequest>,
+) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Coach);
+
+    let mut hooks = appstate.webhooks.lock().unwrap();
+    let id = hooks.last().map_or(1, |h| h.id + 1);
+    let hook = match Webhook::new(id, data.into_inner()) {
+        Ok(h) => h,
+        Err(msg) => return error_response(StatusCode::BAD_REQUEST, "invalid_webhook", msg),
+    };
+    // What a webhook can see is public: the spectator's view.
+    webhooks::spawn(
+        hook.clone(),
+        game.view(Seat::Spectator),
+        game.subscribe(),
+        RetryPolicy::default(),
+    );
+    let json_data = serde_json::to_string(&hook).expect("Error while serializing webhook to JSON.");
+    hooks.push(hook);
+
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json_data)
+}
+
+#[utoipa::path(
+    tag = "game",
+    responses(
+        (status = 200, description = "The current game's webhooks", body = Vec<Webhook>),
+        (status = 401, description = "Missing or unknown seat token", body = ErrorResponse),
+        (status = 403, description = "Spectators can't see webhooks", body = ErrorResponse),
+        (status = 409, description = "No game in progress", body = ErrorResponse),
+    )
+)]
+#[get("/webhooks")]
+async fn get_webhooks(req: HttpRequest, appstate: web::Data<AppState>) -> impl Responder {
+    lock_game!(appstate, game);
+    require_seat!(req, game, seat, SeatAction::Coach);
+    let json_data = serde_json::to_string(&*appstate.webhooks.lock().unwrap())
+        .expect("Error while serializing webhooks to JSON.");
+
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json_data)
+}
+
 #[utoipa::path(
     tag = "game",
     request_body = StartGameRequest,
     responses(
-        (status = 200, description = "Game started; returns initial game state", body = GameState),
-        (status = 404, description = "Unknown team"),
-        (status = 409, description = "A game is already in progress"),
+        (status = 200, description = "Game started; returns the initial game state and the seat tokens", body = StartGameResponse),
+        (status = 404, description = "Unknown team", body = ErrorResponse),
+        (status = 409, description = "A game is already in progress", body = ErrorResponse),
     )
 )]
 #[post("/start")]
@@ -553,17 +1395,26 @@ async fn start_game(
 
     let mut guard = appstate.game.lock().unwrap();
     if guard.is_some() {
-        return HttpResponse::Conflict().body("A game is already in progress");
+        return game_in_progress();
     }
 
-    let game = match Game::create_game(&appstate.env, &req.home, &req.away) {
+    let mut game = match Game::create_game(&appstate.env, &req.home, &req.away, req.options) {
         Ok(g) => g,
         Err(CreateGameError::UnknownTeam(team)) => {
-            return HttpResponse::NotFound().body(format!("Unknown team: {}", team.to_string()))
+            return error_response(
+                StatusCode::NOT_FOUND,
+                "unknown_team",
+                format!("Unknown team: {}", team.to_string()),
+            )
         }
     };
+    persist_new_game_log(&mut game, &appstate.log_dir);
+    let response = StartGameResponse {
+        state: game.state,
+        seats: game.seat_tokens().clone(),
+    };
     let json_data =
-        serde_json::to_string(&game.state).expect("Error while serializing State to JSON.");
+        serde_json::to_string(&response).expect("Error while serializing State to JSON.");
     *guard = Some(game);
 
     HttpResponse::Ok()
@@ -578,10 +1429,15 @@ async fn start_game(
         version = "0.1.0",
         description = "HTTP API for running Statis Pro Football game simulations.\n\n\
                        In addition to the REST endpoints below, the server exposes a \
-                       read-only WebSocket at `GET /game/ws` that streams live `GameEvent`s \
-                       (see the `GameEvent` schema). utoipa cannot describe WebSocket \
+                       WebSocket at `GET /game/ws` that streams live `GameEvent`s \
+                       (see the `GameEvent` schema) and accepts `GameCommand` frames, each \
+                       answered with a `CommandAck`. utoipa cannot describe WebSocket \
                        upgrades natively, so this endpoint does not appear as a path here; \
-                       see the top-level README for a `websocat` usage example."
+                       see the top-level README for a `websocat` usage example. The same \
+                       events are also available as Server-Sent Events at `GET /game/events`.\n\n\
+                       `POST /game/start` returns a token per seat (home, away, spectator). \
+                       Mutating endpoints and the pending lineup/call GETs need one as \
+                       `Authorization: Bearer <token>`; the WebSocket takes `?token=`."
     ),
     servers(
         (url = "http://127.0.0.1:8080", description = "Local dev server")
@@ -592,19 +1448,26 @@ async fn start_game(
         crate::game::standard_play::StandardOffenseCall,
         crate::game::standard_play::StandardDefenseCall,
         crate::game::engine::KickoffOffenseCall,
+        crate::game::engine::KickoffKind,
         crate::game::engine::PuntOffenseCall,
         crate::game::engine::KickoffDefenseCall,
         crate::game::engine::PuntDefenseCall,
+        ErrorResponse,
         GameEvent,
+        SequencedEvent,
+        crate::game::commands::GameCommand,
+        crate::game::commands::CommandAck,
     ))
 )]
 struct ApiDoc;
 
 #[actix_web::main]
-pub async fn runserver(env: GameEnvironment) -> std::io::Result<()> {
+pub async fn runserver(env: GameEnvironment, log_dir: String) -> std::io::Result<()> {
     let app_state = web::Data::new(AppState {
         env,
         game: Mutex::new(None),
+        log_dir,
+        webhooks: Mutex::new(vec![]),
     });
 
     // let game = RefCell::new(game);
@@ -613,7 +1476,7 @@ pub async fn runserver(env: GameEnvironment) -> std::io::Result<()> {
     HttpServer::new(move || {
         let cors = Cors::default()
             .allowed_origin("http://localhost:5173") // <- your vue app origin
-            .allowed_methods(vec!["GET", "POST"]) // <- allow GET and POST requests
+            .allowed_methods(vec!["GET", "POST", "PATCH"]) // <- PATCH for the lineup edits
             .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
             .allowed_header(header::CONTENT_TYPE)
             .max_age(3600)
@@ -623,13 +1486,22 @@ pub async fn runserver(env: GameEnvironment) -> std::io::Result<()> {
             .into_utoipa_app()
             .openapi(ApiDoc::openapi())
             .app_data(app_state.clone())
+            .app_data(web::JsonConfig::default().error_handler(|e, _| bad_request(e)))
+            .app_data(web::QueryConfig::default().error_handler(|e, _| bad_request(e)))
             .map(|a| a.wrap(cors))
             .route("/game/ws", web::get().to(game_ws))
             .service(
                 scope::scope("/game")
                     .service(start_game)
                     .service(get_game_state)
+                    .service(get_formations)
+                    .service(game_events)
+                    .service(add_webhook)
+                    .service(get_webhooks)
                     .service(run_play)
+                    .service(undo_play)
+                    .service(get_game_log)
+                    .service(replay_game)
                     .service(get_all_plays)
                     .service(save_game)
                     .service(get_next_play_types)
@@ -639,13 +1511,23 @@ pub async fn runserver(env: GameEnvironment) -> std::io::Result<()> {
                 scope::scope("/offense")
                     .service(get_offensive_lineup)
                     .service(set_offensive_lineup)
-                    .service(set_offense_call),
+                    .service(validate_offensive_lineup)
+                    .service(change_offensive_lineup)
+                    .service(set_offensive_formation)
+                    .service(save_offense_formation)
+                    .service(set_offense_call)
+                    .service(get_offense_call),
             )
             .service(
                 scope::scope("/defense")
                     .service(get_defensive_lineup)
                     .service(set_defensive_lineup)
-                    .service(set_defense_call),
+                    .service(validate_defensive_lineup)
+                    .service(change_defensive_lineup)
+                    .service(set_defensive_formation)
+                    .service(save_defense_formation)
+                    .service(set_defense_call)
+                    .service(get_defense_call),
             )
             .service(get_player)
             .service(get_team_players)
@@ -657,3 +1539,69 @@ pub async fn runserver(env: GameEnvironment) -> std::io::Result<()> {
     .run()
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{fac::FacManager, testutils};
+    use actix_web::test;
+
+    /// Home has the ball after the opening kickoff and has called its next play; Away
+    /// hasn't answered yet.
+    fn home_has_called() -> (web::Data<AppState>, SeatTokens) {
+        let mut game = testutils::seeded_game(testutils::drive_cards(), 7, GameOptions::default());
+        testutils::kick_off_to_home(&mut game);
+        game.set_offense_call(testutils::handoff()).unwrap();
+        let tokens = game.seat_tokens().clone();
+        let env = GameEnvironment::from_parts(
+            spf_core::players::TeamList::from_rosters(vec![]),
+            FacManager::from_cards(vec![]),
+        );
+        let state = web::Data::new(AppState {
+            env,
+            game: Mutex::new(Some(game)),
+            log_dir: String::new(),
+            webhooks: Mutex::new(vec![]),
+        });
+        (state, tokens)
+    }
+
+    fn read_log(token: &str) -> test::TestRequest {
+        test::TestRequest::get()
+            .uri("/game/log")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+    }
+
+    #[actix_web::test]
+    async fn test_log_hides_the_other_sides_pending_call() {
+        let (state, tokens) = home_has_called();
+        let app = test::init_service(
+            App::new()
+                .app_data(state)
+                .service(web::scope("/game").service(get_game_log)),
+        )
+        .await;
+        let called = |log: &[LogEntry]| matches!(log.last(), Some(LogEntry::OffenseCallSet { .. }));
+        let seed = |log: &[LogEntry]| match log.first() {
+            Some(LogEntry::Created { seed, .. }) => *seed,
+            _ => panic!("the log starts with Created"),
+        };
+
+        let home: Vec<LogEntry> =
+            test::call_and_read_body_json(&app, read_log(&tokens.home).to_request()).await;
+        assert!(called(&home));
+        assert_eq!(seed(&home), None);
+
+        let away: Vec<LogEntry> =
+            test::call_and_read_body_json(&app, read_log(&tokens.away).to_request()).await;
+        assert!(!called(&away));
+        assert!(away.iter().any(|e| matches!(e, LogEntry::PlayRun { .. })));
+        assert_eq!(seed(&away), None);
+
+        let spectator = test::call_service(&app, read_log(&tokens.spectator).to_request()).await;
+        assert_eq!(spectator.status(), StatusCode::FORBIDDEN);
+        let anonymous =
+            test::call_service(&app, test::TestRequest::get().uri("/game/log").to_request()).await;
+        assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+    }
+}
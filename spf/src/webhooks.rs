@@ -0,0 +1,305 @@
+//! Webhooks: a transport adapter that POSTs a game's events to registered URLs, so a chat
+//! bot can announce scoring plays without holding a socket open.
+//!
+//! Each registration gets its own background task subscribed to the game's broadcast
+//! channel (see `docs/design/ws-events-architecture.md`). Events go out one at a time, in
+//! order, as the same spectator-redacted `SequencedEvent` JSON the WebSocket sends. A failed
+//! delivery is retried with exponential backoff and then dropped; a hook that falls more
+//! than the channel's capacity behind skips what it missed. The task ends with the game.
+
+use std::time::Duration;
+
+use actix_web::rt;
+use reqwest::{header::CONTENT_TYPE, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use strum::VariantNames;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use utoipa::ToSchema;
+
+use crate::game::{
+    events::{GameEvent, SequencedEvent},
+    seats::SeatView,
+    GamePlayStatus,
+};
+
+/// One kind of event a webhook wants.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EventFilter {
+    /// The event's name, e.g. `"PlayRun"` or `"GameEnded"`.
+    pub event: String,
+    /// For `PlayRun` only: just the plays that end with this status, e.g. `"Touchdown"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<GamePlayStatus>,
+}
+
+impl EventFilter {
+    fn validate(&self) -> Result<(), String> {
+        if !GameEvent::VARIANTS.contains(&self.event.as_str()) {
+            return Err(format!("Unknown event: {}", self.event));
+        }
+        if self.status.is_some() && self.event != "PlayRun" {
+            return Err(format!(
+                "Only PlayRun can be filtered by status, not {}",
+                self.event
+            ));
+        }
+        Ok(())
+    }
+
+    fn matches(&self, event: &GameEvent) -> bool {
+        if <&str>::from(event) != self.event {
+            return false;
+        }
+        match (self.status, event) {
+            (None, _) => true,
+            (Some(status), GameEvent::PlayRun { play }) => play.new_state.last_status == status,
+            (Some(_), _) => false,
+        }
+    }
+}
+
+/// Body of `POST /game/webhooks`.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct WebhookRequest {
+    /// An `http` or `https` URL that accepts a JSON POST per event.
+    pub url: String,
+    /// The events to send; an event is sent if any filter matches. Empty sends everything.
+    #[serde(default)]
+    pub events: Vec<EventFilter>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Webhook {
+    pub id: u32,
+    pub url: String,
+    pub events: Vec<EventFilter>,
+}
+
+impl Webhook {
+    /// Check a registration and give it `id`.
+    pub fn new(id: u32, req: WebhookRequest) -> Result<Self, String> {
+        let url = Url::parse(&req.url).map_err(|e| format!("{}: {}", req.url, e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("{}: not an http(s) URL", req.url));
+        }
+        for filter in &req.events {
+            filter.validate()?;
+        }
+        Ok(Self {
+            id,
+            url: req.url,
+            events: req.events,
+        })
+    }
+
+    pub fn wants(&self, event: &GameEvent) -> bool {
+        self.events.is_empty() || self.events.iter().any(|f| f.matches(event))
+    }
+}
+
+/// How hard to try before giving up on one event.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    /// The wait after the first failure; doubled after each further one.
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Start delivering `hook`'s events from `rx`, redacted by `view`, until the game is dropped.
+pub fn spawn(hook: Webhook, view: SeatView, mut rx: Receiver<SequencedEvent>, policy: RetryPolicy) {
+    rt::spawn(async move {
+        let client = reqwest::Client::new();
+        loop {
+            let ev = match rx.recv().await {
+                Ok(ev) => ev,
+                Err(RecvError::Lagged(n)) => {
+                    eprintln!("Webhook {} fell behind, skipped {} events", hook.id, n);
+                    continue;
+                }
+                Err(RecvError::Closed) => break, // game dropped
+            };
+            if !hook.wants(&ev.event) {
+                continue;
+            }
+            let Ok(body) = serde_json::to_string(&view.redact(ev)) else {
+                continue;
+            };
+            if let Err(e) = deliver(&client, &hook.url, body, policy).await {
+                eprintln!("Webhook {} gave up on an event: {}", hook.id, e);
+            }
+        }
+    });
+}
+
+/// POST `body` to `url`, retrying transport errors, 5xx and 429 with backoff. Any other
+/// non-2xx answer is final.
+async fn deliver(
+    client: &reqwest::Client,
+    url: &str,
+    body: String,
+    policy: RetryPolicy,
+) -> Result<(), String> {
+    let mut delay = policy.backoff;
+    let mut last_error = String::from("no attempts made");
+    for attempt in 1..=policy.attempts {
+        let res = client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.clone())
+            .send()
+            .await;
+        match res {
+            Ok(r) if r.status().is_success() => return Ok(()),
+            Ok(r)
+                if r.status().is_client_error() && r.status() != StatusCode::TOO_MANY_REQUESTS =>
+            {
+                return Err(format!("{} refused the event: {}", url, r.status()));
+            }
+            Ok(r) => last_error = format!("{} answered {}", url, r.status()),
+            Err(e) => last_error = e.to_string(),
+        }
+        if attempt < policy.attempts {
+            rt::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{engine::PlayType, GameState};
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// A local HTTP stand-in: answers one request per status in `statuses`, in order, and
+    /// returns the request bodies it received.
+    fn receiver(statuses: Vec<u16>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut bodies = vec![];
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = vec![];
+                let mut buf = [0; 4096];
+                // Read until the Content-Length worth of body is in.
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let len = head
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= len {
+                            bodies.push(body.to_string());
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    fn quick() -> RetryPolicy {
+        RetryPolicy {
+            attempts: 3,
+            backoff: Duration::from_millis(10),
+        }
+    }
+
+    fn filter(event: &str, status: Option<GamePlayStatus>) -> EventFilter {
+        EventFilter {
+            event: event.into(),
+            status,
+        }
+    }
+
+    #[test]
+    fn test_filters_match_by_event_name() {
+        let hook = Webhook::new(
+            1,
+            WebhookRequest {
+                url: "https://example.com/hook".into(),
+                events: vec![filter("GameEnded", None)],
+            },
+        )
+        .unwrap();
+        assert!(hook.wants(&GameEvent::GameEnded {
+            state: GameState::start_state()
+        }));
+        assert!(!hook.wants(&GameEvent::NextPlayTypeSet {
+            play_type: PlayType::Kickoff
+        }));
+        assert!(!filter("PlayRun", Some(GamePlayStatus::Touchdown)).matches(
+            &GameEvent::GameEnded {
+                state: GameState::start_state()
+            }
+        ));
+    }
+
+    #[test]
+    fn test_bad_registrations_are_refused() {
+        let request = |url: &str, events| WebhookRequest {
+            url: url.into(),
+            events,
+        };
+        assert!(Webhook::new(1, request("not a url", vec![])).is_err());
+        assert!(Webhook::new(1, request("ftp://example.com", vec![])).is_err());
+        assert!(Webhook::new(1, request("http://x", vec![filter("Fly", None)])).is_err());
+        assert!(Webhook::new(
+            1,
+            request(
+                "http://x",
+                vec![filter("GameEnded", Some(GamePlayStatus::Touchdown))]
+            )
+        )
+        .is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_delivery_retries_server_errors() {
+        let (url, server) = receiver(vec![503, 200]);
+        let res = deliver(&reqwest::Client::new(), &url, "{\"seq\":1}".into(), quick()).await;
+        assert!(res.is_ok(), "{:?}", res);
+        assert_eq!(server.join().unwrap(), vec!["{\"seq\":1}", "{\"seq\":1}"]);
+    }
+
+    #[actix_web::test]
+    async fn test_delivery_gives_up_on_a_client_error() {
+        let (url, server) = receiver(vec![404]);
+        let res = deliver(&reqwest::Client::new(), &url, "{}".into(), quick()).await;
+        assert!(res.is_err());
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+}
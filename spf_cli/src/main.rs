@@ -2,13 +2,18 @@ use std::process::ExitCode;
 
 use clap::{Parser, Subcommand};
 
+use spf::game::calibration::{self, CalibrationReport};
+use spf::game::environment::GameEnvironment;
+use spf::game::simulation::{self, ScoreDistribution, SimulationReport};
+use spf::game::GameOptions;
 use spf_core::persist;
-use spf_core::players::TeamList;
+use spf_core::players::{TeamID, TeamList};
 
 /// Statis Pro Football data tooling.
 ///
 /// Converts the card text files (the output of `pdftotext` over the scanned
-/// PDFs) into the persistent JSON data model consumed by the server.
+/// PDFs) into the persistent JSON data model consumed by the server, and runs
+/// headless batch simulations against that data.
 #[derive(Parser)]
 #[command(name = "spf-cli", version, about)]
 struct Cli {
@@ -32,6 +37,77 @@ enum Command {
         #[arg(long, default_value = "data")]
         out: String,
     },
+
+    /// Simulate games between two teams and report score and player averages.
+    Simulate {
+        /// Home team name (e.g. Chicago).
+        #[arg(long)]
+        home: String,
+
+        /// Away team name (e.g. "N.Y. Giants").
+        #[arg(long)]
+        away: String,
+
+        /// Number of games to play.
+        #[arg(long, default_value_t = 1)]
+        games: u32,
+
+        /// Seed for the FAC shuffles; the same seed replays the same games. Random if omitted.
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// The season both teams are taken from.
+        #[arg(long, default_value = "1983")]
+        year: String,
+
+        /// Play at a neutral site, with no home field for the home team.
+        #[arg(long)]
+        neutral_site: bool,
+
+        /// Root of the converted data. The league is loaded from `<data>/<year>/`.
+        #[arg(long, default_value = "data")]
+        data: String,
+
+        /// FAC deck CSV.
+        #[arg(long, default_value = "cards/fac_cards.csv")]
+        fac: String,
+    },
+
+    /// Simulate games for every team and compare the rates with real season totals.
+    Calibrate {
+        /// Reference CSV of actual team/player season totals (format in
+        /// `spf/src/game/calibration.rs`).
+        #[arg(long)]
+        reference: String,
+
+        /// Games to simulate per team.
+        #[arg(long, default_value_t = 16)]
+        games: u32,
+
+        /// Seed for the FAC shuffles. Random if omitted.
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Output format: `csv` or `json`.
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// Write the report here instead of stdout. Either way, stdout carries nothing else.
+        #[arg(long)]
+        out: Option<String>,
+
+        /// The season to simulate.
+        #[arg(long, default_value = "1983")]
+        year: String,
+
+        /// Root of the converted data. The league is loaded from `<data>/<year>/`.
+        #[arg(long, default_value = "data")]
+        data: String,
+
+        /// FAC deck CSV.
+        #[arg(long, default_value = "cards/fac_cards.csv")]
+        fac: String,
+    },
 }
 
 fn main() -> ExitCode {
@@ -58,6 +134,60 @@ fn main() -> ExitCode {
                 ExitCode::FAILURE
             }
         },
+        Command::Simulate {
+            home,
+            away,
+            games,
+            seed,
+            year,
+            neutral_site,
+            data,
+            fac,
+        } => {
+            spf_core::trace::set_quiet(true);
+            let seed = seed.unwrap_or_else(rand::random);
+            let options = GameOptions {
+                neutral_site,
+                ..GameOptions::default()
+            };
+            let home = TeamID {
+                name: home,
+                year: year.clone(),
+            };
+            let away = TeamID { name: away, year };
+            match simulate(&data, &fac, &home, &away, games, seed, options) {
+                Ok(report) => {
+                    print_report(&report);
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Calibrate {
+            reference,
+            games,
+            seed,
+            format,
+            out,
+            year,
+            data,
+            fac,
+        } => {
+            spf_core::trace::set_quiet(true);
+            let seed = seed.unwrap_or_else(rand::random);
+            let result = calibrate(&data, &year, &fac, &reference, games, seed)
+                .and_then(|report| write_calibration(&report, &format, out.as_deref()));
+            match result {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
     }
 }
 
@@ -68,3 +198,126 @@ fn convert(cards_dir: &str, year: &str, out: &str) -> Result<usize, String> {
     persist::write_league(out, year, &teams)?;
     Ok(count)
 }
+
+fn simulate(
+    data: &str,
+    fac: &str,
+    home: &TeamID,
+    away: &TeamID,
+    games: u32,
+    seed: u64,
+    options: GameOptions,
+) -> Result<SimulationReport, String> {
+    let env = GameEnvironment::load(&format!("{}/{}", data, home.year), fac)?;
+    simulation::simulate_games(&env, home, away, games, seed, options)
+}
+
+fn print_report(report: &SimulationReport) {
+    let games = report.games.max(1) as f64;
+
+    println!();
+    println!(
+        "{} at {}: {} game(s), seed {}",
+        report.away.name, report.home.name, report.games, report.seed
+    );
+    println!(
+        "Record: {} {} - {} {} ({} tie(s))",
+        report.home.name, report.home_wins, report.away.name, report.away_wins, report.ties
+    );
+    print_scores(&report.home.name, &report.home_score);
+    print_scores(&report.away.name, &report.away_score);
+    println!(
+        "(No punts, field goals or extra points yet: scores are touchdowns and safeties only.)"
+    );
+
+    println!();
+    println!(
+        "{:<24} {:<14} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7}",
+        "Player",
+        "Team",
+        "Att/g",
+        "Cmp/g",
+        "PYd/g",
+        "Int/g",
+        "Rsh/g",
+        "RYd/g",
+        "Rec/g",
+        "RcY/g",
+        "DInt/g"
+    );
+    let mut players: Vec<_> = report.players.values().collect();
+    players.sort_by(|a, b| a.team.cmp(&b.team));
+    for p in players {
+        println!(
+            "{:<24} {:<14} {:>7.1} {:>7.1} {:>7.1} {:>7.1} {:>7.1} {:>7.1} {:>7.1} {:>7.1} {:>7.1}",
+            p.name,
+            p.team,
+            p.pass_att as f64 / games,
+            p.pass_cmp as f64 / games,
+            p.pass_yds as f64 / games,
+            p.pass_int as f64 / games,
+            p.rush_att as f64 / games,
+            p.rush_yds as f64 / games,
+            p.rec as f64 / games,
+            p.rec_yds as f64 / games,
+            p.def_int as f64 / games,
+        );
+    }
+}
+
+fn print_scores(team: &str, dist: &ScoreDistribution) {
+    let counts: Vec<String> = dist
+        .counts
+        .iter()
+        .map(|(score, n)| format!("{}x{}", score, n))
+        .collect();
+    println!(
+        "{} points: mean {:.1}, min {}, max {} [{}]",
+        team,
+        dist.mean,
+        dist.min,
+        dist.max,
+        counts.join(" ")
+    );
+}
+
+fn calibrate(
+    data: &str,
+    year: &str,
+    fac: &str,
+    reference: &str,
+    games: u32,
+    seed: u64,
+) -> Result<CalibrationReport, String> {
+    let reference = calibration::read_reference(reference)?;
+    let env = GameEnvironment::load(&format!("{}/{}", data, year), fac)?;
+    calibration::calibrate(&env, &reference, games, seed, GameOptions::default())
+}
+
+fn write_calibration(
+    report: &CalibrationReport,
+    format: &str,
+    out: Option<&str>,
+) -> Result<(), String> {
+    let body = match format {
+        "csv" => report.to_csv()?,
+        "json" => report.to_json()?,
+        _ => return Err(format!("Unknown format: {} (expected csv or json)", format)),
+    };
+    for name in &report.unmatched {
+        eprintln!("warning: no simulated stats for {}", name);
+    }
+    match out {
+        Some(path) => {
+            std::fs::write(path, body).map_err(|e| format!("{}: {}", path, e))?;
+            eprintln!(
+                "Wrote {} row(s) to {} (seed {})",
+                report.rows.len(),
+                path,
+                report.seed
+            );
+        }
+        None => print!("{}", body),
+    }
+    Ok(())
+}
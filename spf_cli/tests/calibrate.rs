@@ -0,0 +1,85 @@
+//! `spf-cli calibrate` writes nothing but the report to stdout, so it can be piped straight
+//! into a file or another tool.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde_json::Value;
+
+const REFERENCE: &str = "\
+team,player,games,points,rush_att,rush_yds,pass_att,pass_cmp,pass_yds,pass_int,sacked,rec,rec_yds
+Chicago,,16,311,571,2727,460,270,3111,23,49,,
+Chicago,Walter Payton,16,,314,1421,,,,,,53,607
+";
+
+/// The workspace root, or `None` (with a note) when the league data isn't checked out.
+fn workspace() -> Option<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    for needed in ["data/1983", "cards/fac_cards.csv"] {
+        if !root.join(needed).exists() {
+            eprintln!("skipping calibrate test: {} not present", needed);
+            return None;
+        }
+    }
+    Some(root)
+}
+
+/// Run a two-game calibration in `format` and return its stdout.
+fn calibrate(root: &Path, format: &str) -> Vec<u8> {
+    let reference = std::env::temp_dir().join(format!(
+        "spf-calibrate-{}-{}.csv",
+        std::process::id(),
+        format
+    ));
+    std::fs::write(&reference, REFERENCE).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_spf-cli"))
+        .current_dir(root)
+        .args([
+            "calibrate",
+            "--games",
+            "2",
+            "--seed",
+            "1",
+            "--format",
+            format,
+        ])
+        .arg("--reference")
+        .arg(&reference)
+        .output()
+        .expect("spf-cli runs");
+    let _ = std::fs::remove_file(&reference);
+    assert!(
+        output.status.success(),
+        "calibrate failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output.stdout
+}
+
+#[test]
+fn test_calibrate_stdout_is_csv() {
+    let Some(root) = workspace() else { return };
+    let stdout = calibrate(&root, "csv");
+
+    let mut reader = csv::Reader::from_reader(stdout.as_slice());
+    let headers = reader.headers().expect("a header row").clone();
+    assert_eq!(&headers[2], "metric");
+    let rows: Vec<csv::StringRecord> = reader
+        .records()
+        .collect::<Result<_, _>>()
+        .expect("every line is a CSV record");
+    assert!(rows.iter().any(|r| &r[1] == "Walter Payton"));
+    assert!(rows.iter().all(|r| r.len() == headers.len()));
+}
+
+#[test]
+fn test_calibrate_stdout_is_json() {
+    let Some(root) = workspace() else { return };
+    let stdout = calibrate(&root, "json");
+
+    let report: Value = serde_json::from_slice(&stdout).expect("stdout is one JSON document");
+    assert_eq!(report["games_per_team"], 2);
+    assert!(!report["rows"].as_array().unwrap().is_empty());
+}
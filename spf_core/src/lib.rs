@@ -11,3 +11,4 @@ pub mod persist;
 pub mod players;
 pub mod shiftable;
 pub mod stats;
+pub mod trace;
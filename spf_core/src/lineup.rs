@@ -1,14 +1,77 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, fmt, str::FromStr};
 
 use itertools::Itertools;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
 use spf_macros::ToBasePlayer;
 use utoipa::ToSchema;
 
 use crate::players::{
-    BasePlayer, DBStats, DLStats, LBStats, OLStats, Player, QBStats, RBStats, Roster, TEStats,
-    ToBasePlayer, WRStats,
+    BasePlayer, DBStats, DLStats, LBStats, OLStats, Player, Position, QBStats, RBStats, Roster,
+    TEStats, ToBasePlayer, WRStats,
 };
+use crate::trace;
+
+/// Why a lineup was refused.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineupError {
+    /// A box that has to be filled is empty.
+    MissingPlayer { position: String },
+    /// The id isn't on the team's roster.
+    UnknownPlayer { position: String, id: String },
+    /// The player can't line up in that box (e.g. a lineman at QB).
+    WrongPosition { position: String, id: String },
+    /// Every player fits their box, but the boxes don't make a legal formation.
+    IllegalFormation(String),
+    /// The team has no depth-chart formation by that name.
+    UnknownFormation(String),
+    /// A substitution names a box the lineup doesn't have, or the wrong kind of value for it.
+    BadSubstitution(String),
+}
+
+impl LineupError {
+    /// A stable, machine-readable name for the kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LineupError::MissingPlayer { .. } => "missing_player",
+            LineupError::UnknownPlayer { .. } => "unknown_player",
+            LineupError::WrongPosition { .. } => "wrong_position",
+            LineupError::IllegalFormation(_) => "illegal_formation",
+            LineupError::UnknownFormation(_) => "unknown_formation",
+            LineupError::BadSubstitution(_) => "bad_substitution",
+        }
+    }
+
+    /// The box the error is about, if it is about one box.
+    pub fn position(&self) -> Option<&str> {
+        match self {
+            LineupError::MissingPlayer { position }
+            | LineupError::UnknownPlayer { position, .. }
+            | LineupError::WrongPosition { position, .. } => Some(position),
+            LineupError::IllegalFormation(_)
+            | LineupError::UnknownFormation(_)
+            | LineupError::BadSubstitution(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for LineupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineupError::MissingPlayer { position } => write!(f, "Missing {}", position),
+            LineupError::UnknownPlayer { position, id } => {
+                write!(f, "No Such {} from {}", position, id)
+            }
+            LineupError::WrongPosition { position, id } => {
+                write!(f, "Not a valid type for {}: {}", position, id)
+            }
+            LineupError::IllegalFormation(msg) | LineupError::BadSubstitution(msg) => {
+                write!(f, "{}", msg)
+            }
+            LineupError::UnknownFormation(name) => write!(f, "No formation named {}", name),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Copy, Eq, Hash, ToSchema)]
 pub enum OffensiveBox {
@@ -160,6 +223,92 @@ pub struct StandardIDOffenseLineup {
     rt: Option<String>,
 }
 
+impl StandardIDOffenseLineup {
+    /// A pro-set lineup from the roster's listed starters (cards list starters first): two
+    /// backs, a flanker, a wide receiver at LE, the tight end at RE and the first five
+    /// linemen. Used when the engine picks lineups itself, e.g. in simulations.
+    pub fn starters(team: &Roster) -> Result<Self, LineupError> {
+        let mut wrs = LineupUtilities::ids_at(team, Position::WR).into_iter();
+        let mut rbs = LineupUtilities::ids_at(team, Position::RB).into_iter();
+        let mut ol = LineupUtilities::ids_at(team, Position::OL).into_iter();
+        let te = LineupUtilities::ids_at(team, Position::TE)
+            .into_iter()
+            .next();
+
+        let qb = LineupUtilities::ids_at(team, Position::QB)
+            .into_iter()
+            .next();
+        let b1 = rbs.next();
+        let b2 = rbs.next();
+        let fl1 = wrs.next();
+        let le = wrs.next();
+        let re = te.or_else(|| wrs.next());
+        // Without a second back, the open spot goes to another flanker.
+        let fl2 = if b2.is_none() { wrs.next() } else { None };
+
+        let lineup = Self {
+            le,
+            re,
+            fl1,
+            fl2,
+            qb,
+            b1,
+            b2,
+            b3: None,
+            lt: ol.next(),
+            lg: ol.next(),
+            c: ol.next(),
+            rg: ol.next(),
+            rt: ol.next(),
+        };
+
+        StandardOffensiveLineup::create_lineup(&lineup, team)?.is_legal_lineup()?;
+        Ok(lineup)
+    }
+
+    /// Everything wrong with this lineup for `team`, where
+    /// [`StandardOffensiveLineup::create_lineup`] and `is_legal_lineup` stop at the first
+    /// problem: each box's player, then the formation. Empty if the lineup is legal.
+    pub fn problems(&self, team: &Roster) -> Vec<LineupError> {
+        type U = LineupUtilities;
+        let boxes = [
+            U::get_player_from_id_or_err(&self.qb, "QB", team, Player::is_qb).err(),
+            U::get_option_player_from_id(&self.le, "LE", team, EndPlayer::gen_from_player).err(),
+            U::get_option_player_from_id(&self.re, "RE", team, EndPlayer::gen_from_player).err(),
+            U::get_option_player_from_id(
+                &self.fl1,
+                "Flanker",
+                team,
+                FlankerPlayer::gen_from_player,
+            )
+            .err(),
+            U::get_option_player_from_id(
+                &self.fl2,
+                "Flanker",
+                team,
+                FlankerPlayer::gen_from_player,
+            )
+            .err(),
+            U::get_option_player_from_id(&self.b1, "B1", team, Player::is_rb).err(),
+            U::get_option_player_from_id(&self.b2, "B2", team, Player::is_rb).err(),
+            U::get_option_player_from_id(&self.b3, "B3", team, Player::is_rb).err(),
+            U::get_player_from_id_or_err(&self.lt, "LT", team, Player::is_ol).err(),
+            U::get_player_from_id_or_err(&self.lg, "LG", team, Player::is_ol).err(),
+            U::get_player_from_id_or_err(&self.c, "C", team, Player::is_ol).err(),
+            U::get_player_from_id_or_err(&self.rg, "RG", team, Player::is_ol).err(),
+            U::get_player_from_id_or_err(&self.rt, "RT", team, Player::is_ol).err(),
+        ];
+        let mut problems: Vec<LineupError> = boxes.into_iter().flatten().collect();
+        problems.extend(StandardOffensiveLineup::formation_problems(
+            U::count_spots(vec![&self.b1, &self.b2, &self.b3]),
+            U::count_spots(vec![&self.le]),
+            U::count_spots(vec![&self.re]),
+            U::count_spots(vec![&self.fl1, &self.fl2]),
+        ));
+        problems
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct StandardOffensiveLineup {
     le: Option<EndPlayer>,
@@ -181,7 +330,7 @@ impl StandardOffensiveLineup {
     pub fn create_lineup(
         id_lineup: &StandardIDOffenseLineup,
         team: &Roster,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, LineupError> {
         let qb =
             LineupUtilities::get_player_from_id_or_err(&id_lineup.qb, "QB", &team, Player::is_qb)?;
 
@@ -265,27 +414,40 @@ impl StandardOffensiveLineup {
         };
     }
 
-    pub fn is_legal_lineup(&self) -> Result<(), String> {
+    pub fn is_legal_lineup(&self) -> Result<(), LineupError> {
         let b_count = LineupUtilities::count_spots(vec![&self.b1, &self.b2, &self.b3]);
-        println!("Backs: {}", b_count);
-        LineupUtilities::validate_count(b_count, 1, 3, "Invalid number of Backs")?;
-
-        let left_end_count = LineupUtilities::count_spots(vec![&self.le]);
-        LineupUtilities::validate_count(left_end_count, 1, 1, "Only one Left End")?;
-
-        let right_end_count = LineupUtilities::count_spots(vec![&self.re]);
-        LineupUtilities::validate_count(right_end_count, 1, 1, "Only one Right End")?;
+        trace!("Backs: {}", b_count);
+        let problems = Self::formation_problems(
+            b_count,
+            LineupUtilities::count_spots(vec![&self.le]),
+            LineupUtilities::count_spots(vec![&self.re]),
+            LineupUtilities::count_spots(vec![&self.fl1, &self.fl2]),
+        );
+        LineupUtilities::first(problems)
+    }
 
-        let flanker_count = LineupUtilities::count_spots(vec![&self.fl1, &self.fl2]);
+    /// The formation rules, from how many of each kind of box are filled.
+    fn formation_problems(
+        b_count: i32,
+        left_end_count: i32,
+        right_end_count: i32,
+        flanker_count: i32,
+    ) -> Vec<LineupError> {
         let remaining_spots = 3 - b_count;
-        LineupUtilities::validate_count(
-            flanker_count,
-            remaining_spots,
-            remaining_spots,
-            "Invalid number of Flankers",
-        )?;
-
-        return Ok(());
+        [
+            LineupUtilities::validate_count(b_count, 1, 3, "Invalid number of Backs"),
+            LineupUtilities::validate_count(left_end_count, 1, 1, "Only one Left End"),
+            LineupUtilities::validate_count(right_end_count, 1, 1, "Only one Right End"),
+            LineupUtilities::validate_count(
+                flanker_count,
+                remaining_spots,
+                remaining_spots,
+                "Invalid number of Flankers",
+            ),
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect()
     }
 
     pub fn get_player_in_pos(&self, spot: &OffensiveBox) -> Option<&dyn BasePlayer> {
@@ -335,6 +497,126 @@ pub struct StandardIDDefenseLineup {
     box_o: Option<String>,
 }
 
+impl StandardIDDefenseLineup {
+    /// A 4-3 lineup from the roster's listed starters: linemen in boxes A, B, D and E,
+    /// linebackers in F, H and J and four defensive backs in K, M, N and O.
+    pub fn starters(team: &Roster) -> Result<Self, LineupError> {
+        let mut dl = LineupUtilities::ids_at(team, Position::DL).into_iter();
+        let mut lb = LineupUtilities::ids_at(team, Position::LB).into_iter();
+        let mut db = LineupUtilities::ids_at(team, Position::DB).into_iter();
+
+        let lineup = Self {
+            box_a: dl.next().into_iter().collect(),
+            box_b: dl.next().into_iter().collect(),
+            box_c: vec![],
+            box_d: dl.next().into_iter().collect(),
+            box_e: dl.next().into_iter().collect(),
+            box_f: lb.next(),
+            box_g: None,
+            box_h: lb.next(),
+            box_i: None,
+            box_j: lb.next(),
+            box_k: db.next(),
+            box_l: vec![],
+            box_m: db.next(),
+            box_n: db.next(),
+            box_o: db.next(),
+        };
+
+        StandardDefensiveLineup::create_lineup(&lineup, team)?.is_legal_lineup()?;
+        Ok(lineup)
+    }
+
+    /// Everything wrong with this lineup for `team`: every player who can't play their box
+    /// (each one in the multi-player boxes), then the formation. Empty if the lineup is legal.
+    pub fn problems(&self, team: &Roster) -> Vec<LineupError> {
+        type U = LineupUtilities;
+        let mut problems = vec![];
+        for (ids, name) in [
+            (&self.box_a, "box_a"),
+            (&self.box_b, "box_b"),
+            (&self.box_c, "box_c"),
+            (&self.box_d, "box_d"),
+            (&self.box_e, "box_e"),
+        ] {
+            problems.extend(U::vector_problems(
+                ids,
+                name,
+                team,
+                Row1Player::gen_from_player,
+            ));
+        }
+        for (id, name) in [
+            (&self.box_f, "box_f"),
+            (&self.box_g, "box_g"),
+            (&self.box_h, "box_h"),
+            (&self.box_i, "box_i"),
+            (&self.box_j, "box_j"),
+        ] {
+            problems.extend(U::get_option_player_from_id(id, name, team, Player::is_lb).err());
+        }
+        for (id, name) in [
+            (&self.box_k, "box_k"),
+            (&self.box_m, "box_m"),
+            (&self.box_n, "box_n"),
+            (&self.box_o, "box_o"),
+        ] {
+            problems.extend(U::get_option_player_from_id(id, name, team, Player::is_db).err());
+        }
+        problems.extend(U::vector_problems(
+            &self.box_l,
+            "box_l",
+            team,
+            Player::is_db,
+        ));
+
+        problems.extend(StandardDefensiveLineup::formation_problems(
+            [
+                self.box_a.len(),
+                self.box_b.len(),
+                self.box_c.len(),
+                self.box_d.len(),
+                self.box_e.len(),
+            ],
+            self.count_in_row(DefensiveRow::Row2),
+            U::count_spots(vec![&self.box_k, &self.box_m, &self.box_n, &self.box_o]),
+            self.box_l.len() as i32,
+        ));
+        problems
+    }
+
+    /// How many ids are in `row`, like [`StandardDefensiveLineup::get_count_in_row`].
+    pub fn count_in_row(&self, row: DefensiveRow) -> i32 {
+        match row {
+            DefensiveRow::Row1 => [
+                &self.box_a,
+                &self.box_b,
+                &self.box_c,
+                &self.box_d,
+                &self.box_e,
+            ]
+            .iter()
+            .map(|b| b.len() as i32)
+            .sum(),
+            DefensiveRow::Row2 => LineupUtilities::count_spots(vec![
+                &self.box_f,
+                &self.box_g,
+                &self.box_h,
+                &self.box_i,
+                &self.box_j,
+            ]),
+            DefensiveRow::Row3 => {
+                LineupUtilities::count_spots(vec![
+                    &self.box_k,
+                    &self.box_m,
+                    &self.box_n,
+                    &self.box_o,
+                ]) + self.box_l.len() as i32
+            }
+        }
+    }
+}
+
 pub enum DefensiveRow {
     Row1,
     Row2,
@@ -379,7 +661,7 @@ impl StandardDefensiveLineup {
     pub fn create_lineup(
         id_lineup: &StandardIDDefenseLineup,
         team: &Roster,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, LineupError> {
         let box_a = LineupUtilities::transform_vector(
             &id_lineup.box_a,
             "box_a",
@@ -539,50 +821,69 @@ impl StandardDefensiveLineup {
             }
         }
     }
-    pub fn is_legal_lineup(&self) -> Result<(), String> {
-        let row1_spots = LineupUtilities::count_array_spots(
-            vec![
-                &self.box_a,
-                &self.box_b,
-                &self.box_c,
-                &self.box_d,
-                &self.box_e,
+    pub fn is_legal_lineup(&self) -> Result<(), LineupError> {
+        let problems = Self::formation_problems(
+            [
+                self.box_a.len(),
+                self.box_b.len(),
+                self.box_c.len(),
+                self.box_d.len(),
+                self.box_e.len(),
             ],
-            3,
-            "Only 3 allowed in a First Row Box",
-        )?;
-
-        LineupUtilities::validate_count(row1_spots, 3, 10, "Need between 3-10 in First Row")?;
+            self.get_count_in_row(DefensiveRow::Row2),
+            LineupUtilities::count_spots(vec![&self.box_k, &self.box_m, &self.box_n, &self.box_o]),
+            self.box_l.len() as i32,
+        );
+        LineupUtilities::first(problems)
+    }
 
-        let row2_spots = LineupUtilities::count_spots(vec![
-            &self.box_f,
-            &self.box_g,
-            &self.box_h,
-            &self.box_i,
-            &self.box_j,
-        ]);
+    /// The formation rules, from the sizes of the first-row boxes (A-E), the number of
+    /// linebackers, and the number of backs outside and inside box L.
+    fn formation_problems(
+        row1_boxes: [usize; 5],
+        row2_spots: i32,
+        non_box_l_db_count: i32,
+        l_count: i32,
+    ) -> Vec<LineupError> {
+        let mut problems = vec![];
+        if row1_boxes.iter().any(|&n| n > 3) {
+            problems.push(LineupError::IllegalFormation(
+                "Only 3 allowed in a First Row Box".to_string(),
+            ));
+        }
+        let row1_spots = row1_boxes.iter().sum::<usize>() as i32;
+        problems.extend(
+            LineupUtilities::validate_count(row1_spots, 3, 10, "Need between 3-10 in First Row")
+                .err(),
+        );
 
         let remaining_row3_spots = 11 - (row2_spots + row1_spots);
 
         if remaining_row3_spots < 0 {
-            return Err("Too many Lineman and Linebackers".to_string());
+            problems.push(LineupError::IllegalFormation(
+                "Too many Lineman and Linebackers".to_string(),
+            ));
         }
 
-        let non_box_l_db_count =
-            LineupUtilities::count_spots(vec![&self.box_k, &self.box_m, &self.box_n, &self.box_o]);
-        let l_count = self.box_l.len() as i32;
         if l_count > 0 && non_box_l_db_count < 4 {
-            return Err("Can only put in Box L after the other 4 Row 3 spots are full".to_string());
+            problems.push(LineupError::IllegalFormation(
+                "Can only put in Box L after the other 4 Row 3 spots are full".to_string(),
+            ));
         }
 
-        LineupUtilities::validate_count(
-            non_box_l_db_count + l_count,
-            remaining_row3_spots,
-            remaining_row3_spots,
-            "Improper secondary size",
-        )?;
+        if remaining_row3_spots >= 0 {
+            problems.extend(
+                LineupUtilities::validate_count(
+                    non_box_l_db_count + l_count,
+                    remaining_row3_spots,
+                    remaining_row3_spots,
+                    "Improper secondary size",
+                )
+                .err(),
+            );
+        }
 
-        return Ok(());
+        problems
     }
 
     pub fn find_player(&self, id: &String) -> Option<DefensiveBox> {
@@ -716,6 +1017,10 @@ impl StandardDefensiveLineup {
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 pub struct KickoffIDOffenseLineup {
     pub k: String,
+    /// Tacklers (LB or DL) covering the kick. With none named the return is left to the
+    /// returner's card.
+    #[serde(default)]
+    pub coverage: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
@@ -723,8 +1028,136 @@ pub struct KickoffIDDefenseLineup {
     pub kr: String,
 }
 
+/// A team's named lineups (e.g. "I-form", "3WR", "4-3 base", "nickel"), saved with its
+/// team data so coaches don't re-enter them every play.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct DepthChart {
+    #[serde(default)]
+    pub offense: BTreeMap<String, StandardIDOffenseLineup>,
+    #[serde(default)]
+    pub defense: BTreeMap<String, StandardIDDefenseLineup>,
+}
+
+impl DepthChart {
+    pub fn is_empty(&self) -> bool {
+        self.offense.is_empty() && self.defense.is_empty()
+    }
+
+    /// The named offensive formation with `pick`'s substitutions applied.
+    pub fn offense_lineup(
+        &self,
+        pick: &FormationLineup,
+    ) -> Result<StandardIDOffenseLineup, LineupError> {
+        let lineup = self
+            .offense
+            .get(&pick.name)
+            .ok_or_else(|| LineupError::UnknownFormation(pick.name.clone()))?;
+        substitute(lineup, &pick.substitutions)
+    }
+
+    /// The named defensive formation with `pick`'s substitutions applied.
+    pub fn defense_lineup(
+        &self,
+        pick: &FormationLineup,
+    ) -> Result<StandardIDDefenseLineup, LineupError> {
+        let lineup = self
+            .defense
+            .get(&pick.name)
+            .ok_or_else(|| LineupError::UnknownFormation(pick.name.clone()))?;
+        substitute(lineup, &pick.substitutions)
+    }
+}
+
+/// A lineup given as a depth-chart formation plus this play's substitutions.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FormationLineup {
+    pub name: String,
+    /// Boxes to change for this play, by the lineup's field names: `{"b1": "RB-2",
+    /// "fl2": null}` or `{"box_l": ["DB-5"]}`.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub substitutions: Map<String, Value>,
+}
+
+/// `lineup` with the boxes in `substitutions` replaced. Each key must be one of the
+/// lineup's fields and each value must fit it; whether the result is legal is up to the
+/// usual checks.
+pub fn substitute<T: Serialize + DeserializeOwned>(
+    lineup: &T,
+    substitutions: &Map<String, Value>,
+) -> Result<T, LineupError> {
+    let bad = |e: serde_json::Error| LineupError::BadSubstitution(e.to_string());
+    let mut value = serde_json::to_value(lineup).map_err(bad)?;
+    let boxes = value
+        .as_object_mut()
+        .ok_or_else(|| LineupError::BadSubstitution("Lineup has no boxes".to_string()))?;
+    for (name, ids) in substitutions {
+        let slot = boxes
+            .get_mut(name)
+            .ok_or_else(|| LineupError::BadSubstitution(format!("No box named {}", name)))?;
+        *slot = ids.clone();
+    }
+    serde_json::from_value(value).map_err(bad)
+}
+
+/// Changes to a lineup that is already set: players swapped for others wherever they line
+/// up, then boxes refilled.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct LineupChanges {
+    /// Outgoing player id to incoming player id: `{"RB-1": "RB-3"}`.
+    #[serde(default)]
+    pub players: BTreeMap<String, String>,
+    /// Boxes to change, as in [`FormationLineup::substitutions`].
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub boxes: Map<String, Value>,
+}
+
+impl LineupChanges {
+    /// `lineup` with these changes made. Every outgoing player must be in it; whether the
+    /// result is legal is up to the usual checks.
+    pub fn apply<T: Serialize + DeserializeOwned>(&self, lineup: &T) -> Result<T, LineupError> {
+        substitute(&swap_players(lineup, &self.players)?, &self.boxes)
+    }
+}
+
+/// `lineup` with each player in `swaps` replaced by their substitute. Swaps are made all at
+/// once, so two players can trade boxes.
+fn swap_players<T: Serialize + DeserializeOwned>(
+    lineup: &T,
+    swaps: &BTreeMap<String, String>,
+) -> Result<T, LineupError> {
+    let bad = |e: serde_json::Error| LineupError::BadSubstitution(e.to_string());
+    let mut value = serde_json::to_value(lineup).map_err(bad)?;
+    let boxes = value
+        .as_object_mut()
+        .ok_or_else(|| LineupError::BadSubstitution("Lineup has no boxes".to_string()))?;
+    let mut missing: Vec<&String> = swaps.keys().collect();
+    let ids = boxes.values_mut().flat_map(|slot| match slot {
+        Value::Array(ids) => ids.iter_mut().collect(),
+        id => vec![id],
+    });
+    for id in ids {
+        if let Some(incoming) = id.as_str().and_then(|out| swaps.get(out)) {
+            missing.retain(|out| Some(out.as_str()) != id.as_str());
+            *id = Value::String(incoming.clone());
+        }
+    }
+    if let Some(out) = missing.first() {
+        return Err(LineupError::BadSubstitution(format!(
+            "{} is not in the lineup",
+            out
+        )));
+    }
+    serde_json::from_value(value).map_err(bad)
+}
+
 struct LineupUtilities {}
 impl LineupUtilities {
+    fn ids_at(team: &Roster, pos: Position) -> Vec<String> {
+        team.get_players(pos).iter().map(|p| p.get_id()).collect()
+    }
+
     fn get_ids_for_vec<T: ToBasePlayer>(players: &Vec<T>) -> Vec<String> {
         players
             .into_iter()
@@ -765,45 +1198,56 @@ impl LineupUtilities {
         pos_str: &str,
         team: &Roster,
         transform: F,
-    ) -> Result<T, String>
+    ) -> Result<T, LineupError>
     where
         F: Fn(Player) -> Option<T>,
     {
-        // let id = &id_opt.ok_or(format!("Missing {}", pos_str))?;
-
         let id = match id_opt {
-            None => return Err(format!("Missing {}", pos_str)),
+            None => {
+                return Err(LineupError::MissingPlayer {
+                    position: pos_str.to_string(),
+                })
+            }
             Some(val) => val,
         };
 
-        let p = team.get_player(id).ok_or(format!("No Such {}", pos_str))?;
-        let t =
-            transform(p.get_full_player()).ok_or(format!("Not a valid type for {}", pos_str))?;
-        return Ok(t);
+        LineupUtilities::transform_player(id, pos_str, team, transform)
     }
 
-    fn get_option_player_from_id<T, F>(
-        id_opt: &Option<String>,
+    fn transform_player<T, F>(
+        id: &String,
         pos_str: &str,
         team: &Roster,
         transform: F,
-    ) -> Result<Option<T>, String>
+    ) -> Result<T, LineupError>
     where
         F: Fn(Player) -> Option<T>,
     {
-        // let id = &id_opt.ok_or(format!("Missing {}", pos_str))?;
-
-        let id = match id_opt {
-            None => return Ok(None),
-            Some(val) => val,
-        };
-
         let p = team
             .get_player(id)
-            .ok_or(format!("No Such {} from {}", pos_str, id))?;
-        let t =
-            transform(p.get_full_player()).ok_or(format!("Not a valid type for {}", pos_str))?;
-        return Ok(Some(t));
+            .ok_or_else(|| LineupError::UnknownPlayer {
+                position: pos_str.to_string(),
+                id: id.clone(),
+            })?;
+        transform(p.get_full_player()).ok_or_else(|| LineupError::WrongPosition {
+            position: pos_str.to_string(),
+            id: id.clone(),
+        })
+    }
+
+    fn get_option_player_from_id<T, F>(
+        id_opt: &Option<String>,
+        pos_str: &str,
+        team: &Roster,
+        transform: F,
+    ) -> Result<Option<T>, LineupError>
+    where
+        F: Fn(Player) -> Option<T>,
+    {
+        match id_opt {
+            None => Ok(None),
+            Some(id) => LineupUtilities::transform_player(id, pos_str, team, transform).map(Some),
+        }
     }
 
     // unused: by-value twin of the used `get_player_from_id_or_err`; kept pending removal.
@@ -813,18 +1257,11 @@ impl LineupUtilities {
         pos_str: &str,
         team: &Roster,
         transform: F,
-    ) -> Result<T, String>
+    ) -> Result<T, LineupError>
     where
         F: Fn(Player) -> Option<T>,
     {
-        let id = &id_opt.ok_or(format!("Missing {}", pos_str))?;
-
-        let p = team
-            .get_player(id)
-            .ok_or(format!("No Such {} with id {}", pos_str, id))?;
-        let t =
-            transform(p.get_full_player()).ok_or(format!("Not a valid type for {}", pos_str))?;
-        return Ok(t);
+        LineupUtilities::get_player_from_id_or_err(&id_opt, pos_str, team, transform)
     }
 
     fn transform_vector<T, F>(
@@ -832,7 +1269,7 @@ impl LineupUtilities {
         pos_str: &str,
         team: &Roster,
         transform: F,
-    ) -> Result<Vec<T>, String>
+    ) -> Result<Vec<T>, LineupError>
     where
         F: Fn(Player) -> Option<T>,
     {
@@ -846,11 +1283,33 @@ impl LineupUtilities {
                     &transform,
                 )
             })
-            .collect::<Result<Vec<T>, String>>();
+            .collect::<Result<Vec<T>, LineupError>>();
 
         return v;
     }
 
+    /// Like [`Self::transform_vector`], but checks every id instead of stopping at the first
+    /// bad one.
+    fn vector_problems<T, F>(
+        id_vecs: &[String],
+        pos_str: &str,
+        team: &Roster,
+        transform: F,
+    ) -> Vec<LineupError>
+    where
+        F: Fn(Player) -> Option<T>,
+    {
+        id_vecs
+            .iter()
+            .filter_map(|id| LineupUtilities::transform_player(id, pos_str, team, &transform).err())
+            .collect()
+    }
+
+    /// The first of a list of problems as a result, for the checks that stop at one.
+    fn first(problems: Vec<LineupError>) -> Result<(), LineupError> {
+        problems.into_iter().next().map_or(Ok(()), Err)
+    }
+
     fn convert_vec_to_base_player<'a, T: ToBasePlayer>(v: &'a Vec<T>) -> Vec<&'a dyn BasePlayer> {
         v.iter().map(ToBasePlayer::get_player).collect()
     }
@@ -862,10 +1321,10 @@ impl LineupUtilities {
         }
     }
 
-    fn validate_count(actual: i32, low: i32, high: i32, msg: &str) -> Result<(), String> {
+    fn validate_count(actual: i32, low: i32, high: i32, msg: &str) -> Result<(), LineupError> {
         if actual < low || actual > high {
             let m = format!("{}: Expected {}-{} but was {}", msg, low, high, actual);
-            return Err(m);
+            return Err(LineupError::IllegalFormation(m));
         }
         return Ok(());
     }
@@ -875,11 +1334,15 @@ impl LineupUtilities {
         pos.iter().map(|t| if t.is_some() { 1 } else { 0 }).sum()
     }
 
-    fn count_array_spots<T>(pos: Vec<&Vec<T>>, max_per: i32, msg: &str) -> Result<i32, String> {
+    fn count_array_spots<T>(
+        pos: Vec<&Vec<T>>,
+        max_per: i32,
+        msg: &str,
+    ) -> Result<i32, LineupError> {
         let total = pos.iter().try_fold(0, |acc, item| {
             let count = item.len() as i32;
             if count > max_per {
-                return Err(msg.to_string());
+                return Err(LineupError::IllegalFormation(msg.to_string()));
             }
             Ok(acc + count)
         })?;
@@ -894,9 +1357,25 @@ mod tests {
     //!
     //! Covers the box `from_str` parsers and the `LineupUtilities` count/validation
     //! helpers. These are pure and dependency-free (no fixtures, no I/O). Full
-    //! `is_legal_lineup` coverage needs `Standard*Lineup` builders and is deferred.
+    //! `is_legal_lineup` coverage needs `Standard*Lineup` builders and is deferred; the
+    //! `problems` reports are checked against an empty roster.
 
     use super::*;
+    use crate::players::TeamID;
+
+    fn empty_roster() -> Roster {
+        Roster::from_players(
+            TeamID {
+                name: "Empty".into(),
+                year: "1983".into(),
+            },
+            vec![],
+        )
+    }
+
+    fn id(s: &str) -> Option<String> {
+        Some(s.to_string())
+    }
 
     #[test]
     fn test_offensive_box_from_str_maps_all_aliases() {
@@ -1014,6 +1493,8 @@ mod tests {
     fn test_validate_count_rejects_out_of_range() {
         assert!(LineupUtilities::validate_count(0, 1, 3, "too low").is_err());
         assert!(LineupUtilities::validate_count(4, 1, 3, "too high").is_err());
+        let err = LineupUtilities::validate_count(4, 1, 3, "too high").unwrap_err();
+        assert_eq!(err.code(), "illegal_formation");
     }
 
     #[test]
@@ -1043,4 +1524,138 @@ mod tests {
             "a single array exceeding max_per must error"
         );
     }
+
+    #[test]
+    fn test_offense_problems_lists_every_box_and_the_formation() {
+        let lineup = StandardIDOffenseLineup {
+            le: None,
+            re: None,
+            fl1: id("WR-1"),
+            fl2: None,
+            qb: id("QB-1"),
+            b1: id("RB-1"),
+            b2: id("RB-2"),
+            b3: id("RB-3"),
+            lt: None,
+            lg: None,
+            c: None,
+            rg: None,
+            rt: None,
+        };
+        let problems = lineup.problems(&empty_roster());
+        let count = |code: &str| problems.iter().filter(|p| p.code() == code).count();
+        // QB, a flanker and three backs aren't on the roster; five linemen are missing.
+        assert_eq!(count("unknown_player"), 5);
+        assert_eq!(count("missing_player"), 5);
+        // No ends, and a flanker with three backs.
+        assert_eq!(count("illegal_formation"), 3);
+        assert_eq!(problems[0].position(), Some("QB"));
+    }
+
+    #[test]
+    fn test_defense_problems_checks_each_player_in_a_shared_box() {
+        let lineup = StandardIDDefenseLineup {
+            box_a: vec!["DL-1".into(), "DL-2".into(), "DL-3".into(), "DL-4".into()],
+            box_b: vec![],
+            box_c: vec![],
+            box_d: vec![],
+            box_e: vec![],
+            box_f: None,
+            box_g: None,
+            box_h: None,
+            box_i: None,
+            box_j: None,
+            box_k: None,
+            box_l: vec!["DB-1".into()],
+            box_m: None,
+            box_n: None,
+            box_o: None,
+        };
+        let problems = lineup.problems(&empty_roster());
+        assert_eq!(
+            problems
+                .iter()
+                .filter(|p| p.position() == Some("box_a"))
+                .count(),
+            4
+        );
+        let formation: Vec<String> = problems
+            .iter()
+            .filter(|p| p.code() == "illegal_formation")
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(
+            formation,
+            vec![
+                "Only 3 allowed in a First Row Box",
+                "Can only put in Box L after the other 4 Row 3 spots are full",
+                "Improper secondary size: Expected 7-7 but was 1",
+            ]
+        );
+        assert_eq!(lineup.count_in_row(DefensiveRow::Row1), 4);
+    }
+
+    #[test]
+    fn test_substitute_replaces_only_the_named_boxes() {
+        let lineup = StandardIDOffenseLineup {
+            le: id("WR-1"),
+            re: id("TE-1"),
+            fl1: id("WR-2"),
+            fl2: None,
+            qb: id("QB-1"),
+            b1: id("RB-1"),
+            b2: id("RB-2"),
+            b3: None,
+            lt: id("OL-1"),
+            lg: id("OL-2"),
+            c: id("OL-3"),
+            rg: id("OL-4"),
+            rt: id("OL-5"),
+        };
+        let subs = |v: serde_json::Value| v.as_object().unwrap().clone();
+
+        let swapped = substitute(
+            &lineup,
+            &subs(serde_json::json!({"b2": null, "fl2": "WR-3"})),
+        )
+        .unwrap();
+        assert_eq!(swapped.b2, None);
+        assert_eq!(swapped.fl2, id("WR-3"));
+        assert_eq!(swapped.qb, id("QB-1"));
+
+        let err = substitute(&lineup, &subs(serde_json::json!({"box_a": ["DL-1"]}))).unwrap_err();
+        assert_eq!(err.code(), "bad_substitution");
+        let err = substitute(&lineup, &subs(serde_json::json!({"qb": ["QB-1"]}))).unwrap_err();
+        assert_eq!(err.code(), "bad_substitution");
+    }
+
+    #[test]
+    fn test_changes_swap_players_wherever_they_line_up() {
+        let lineup: StandardIDDefenseLineup = serde_json::from_value(serde_json::json!({
+            "box_a": ["DL-1"], "box_b": ["DL-2", "DL-3"], "box_c": ["DL-4"], "box_d": [],
+            "box_e": [], "box_f": "LB-1", "box_l": [], "box_m": "DB-1", "box_n": "DB-2"
+        }))
+        .unwrap();
+        let changes = |v: serde_json::Value| serde_json::from_value::<LineupChanges>(v).unwrap();
+
+        let changed = changes(serde_json::json!({
+            "players": {"DL-3": "DL-9", "DB-1": "DB-2", "DB-2": "DB-1"},
+            "boxes": {"box_c": []}
+        }))
+        .apply(&lineup)
+        .unwrap();
+        assert_eq!(changed.box_b, vec!["DL-2", "DL-9"]);
+        assert_eq!(changed.box_m, id("DB-2"));
+        assert_eq!(changed.box_n, id("DB-1"));
+        assert!(changed.box_c.is_empty());
+        assert_eq!(changed.box_f, id("LB-1"));
+
+        let err = changes(serde_json::json!({"players": {"LB-7": "LB-8"}}))
+            .apply(&lineup)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            LineupError::BadSubstitution("LB-7 is not in the lineup".into())
+        );
+    }
 }
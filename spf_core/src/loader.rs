@@ -1,4 +1,4 @@
-use crate::players::{DBStats, QBStats, RBStats, TeamID, WRStats};
+use crate::players::{DBStats, QBStats, RBStats, TeamID, TeamStats, WRStats};
 use std::fs;
 
 use crate::players::Position;
@@ -411,6 +411,62 @@ fn parse_pr_record((id, lines): (String, &[&str])) -> Option<PRStats> {
     })
 }
 
+/// Team cards differ in length (playoff teams carry an extra line), so records are split at
+/// the year line that opens each card instead of by a fixed size.
+pub fn load_teams(filename: String) -> Vec<TeamStats> {
+    let file_contents = fs::read_to_string(filename).unwrap();
+    let lines = file_contents.lines().collect::<Vec<&str>>();
+
+    let starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.len() == 4 && l.chars().all(|c| c.is_ascii_digit()))
+        .map(|(i, _)| i)
+        .collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(lines.len());
+            parse_team_record(&lines[start..end])
+        })
+        .collect()
+}
+
+fn parse_team_record(lines: &[&str]) -> Option<TeamStats> {
+    // The two-team cities only give the city; the nickname line tells them apart.
+    let nickname = lines.get(2)?.split_whitespace().next().unwrap_or("");
+    let city = match lines.get(1)? {
+        &"L.A" => format!("L.A. {}", nickname),
+        &"New York" => format!("N.Y. {}", nickname),
+        city => city.to_string(),
+    };
+    let team = TeamID::create_from_str(&format!("{} {}", lines[0], city));
+
+    let after = |label: &str| {
+        lines
+            .iter()
+            .position(|l| *l == label)
+            .and_then(|i| lines.get(i + 1))
+    };
+    let starting = |prefix: &str| lines.iter().find(|l| l.starts_with(prefix));
+
+    let big_plays: Vec<&str> = after("Big Plays")?.split_whitespace().collect();
+    let big_play_home = get_i32(big_plays.get(1)?, 0);
+    let big_play_road = get_i32(big_plays.get(3)?, 0);
+    let fumbles_lost = Range::from_str(get_val(starting("Lost:")?).unwrap_or(""));
+    let def_adj = get_i32_from_val(starting("Def.Adj:")?, 0);
+
+    Some(TeamStats {
+        team,
+        big_play_home,
+        big_play_road,
+        fumbles_lost,
+        def_adj,
+    })
+}
+
 fn parse_records<T, F>(
     filename: String,
     size: usize,
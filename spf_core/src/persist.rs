@@ -3,7 +3,7 @@
 //! Layout on disk:
 //! ```text
 //! <root>/<year>/index.json      # LeagueManifest: lists every team file
-//! <root>/<year>/<TeamName>.json # TeamData: one team's full roster
+//! <root>/<year>/<TeamName>.json # TeamData: one team's full roster and depth chart
 //! ```
 //!
 //! The player list inside each team file is a JSON array of the internally
@@ -17,7 +17,8 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::players::{Player, Roster, TeamID, TeamList};
+use crate::lineup::DepthChart;
+use crate::players::{Player, Roster, TeamID, TeamList, TeamStats};
 
 pub const FORMAT_VERSION: u32 = 1;
 pub const MANIFEST_FILE: &str = "index.json";
@@ -27,6 +28,34 @@ pub const MANIFEST_FILE: &str = "index.json";
 pub struct TeamData {
     pub team: TeamID,
     pub players: Vec<Player>,
+    /// Named lineups saved by the team's coaches. Absent in files that predate them.
+    #[serde(default, skip_serializing_if = "DepthChart::is_empty")]
+    pub depth_chart: DepthChart,
+    /// The team card. Absent in files that predate it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_card: Option<TeamStats>,
+}
+
+impl TeamData {
+    /// The persistent form of an in-memory roster.
+    pub fn from_roster(roster: &Roster) -> Self {
+        Self {
+            team: roster.get_team_name().clone(),
+            players: roster
+                .get_all_players()
+                .iter()
+                .map(|p| p.get_full_player())
+                .collect(),
+            depth_chart: roster.depth_chart().clone(),
+            team_card: roster.team_card().cloned(),
+        }
+    }
+
+    pub fn into_roster(self) -> Roster {
+        Roster::from_players(self.team, self.players)
+            .with_depth_chart(self.depth_chart)
+            .with_team_card(self.team_card)
+    }
 }
 
 /// An entry in the league manifest pointing at a single team file.
@@ -73,15 +102,9 @@ pub fn write_league(root: &str, year: &str, teams: &TeamList) -> Result<(), Stri
     let mut entries: Vec<TeamEntry> = Vec::new();
 
     for (team_id, roster) in &teams.teams {
-        let players: Vec<Player> = roster
-            .get_all_players()
-            .iter()
-            .map(|p| p.get_full_player())
-            .collect();
-
         let data = TeamData {
             team: team_id.clone(),
-            players,
+            ..TeamData::from_roster(roster)
         };
 
         let file_name = format!("{}.json", sanitize_file_stem(&team_id.name));
@@ -122,15 +145,33 @@ pub fn load_team(path: &Path) -> Result<Roster, String> {
         fs::read_to_string(path).map_err(|e| format!("Reading {}: {}", path.display(), e))?;
     let data: TeamData =
         serde_json::from_str(&json).map_err(|e| format!("Parsing {}: {}", path.display(), e))?;
-    Ok(Roster::from_players(data.team, data.players))
+    Ok(data.into_roster())
 }
 
-/// Load a full [`TeamList`] from a year directory (e.g. `data/1983`).
-///
-/// Reads the manifest, then each referenced team file, and rebuilds the runtime
-/// model (including the per-position id lookup maps).
-pub fn load_league(year_dir: &str) -> Result<TeamList, String> {
+/// Replace one team's depth chart in its file under a year directory (e.g. `data/1983`),
+/// leaving its players untouched.
+pub fn save_depth_chart(year_dir: &str, team: &TeamID, chart: &DepthChart) -> Result<(), String> {
     let dir = Path::new(year_dir);
+    let manifest = read_manifest(dir)?;
+    let entry = manifest
+        .teams
+        .iter()
+        .find(|t| t.name == team.name && t.year == team.year)
+        .ok_or_else(|| format!("{} is not in {}", team.to_string(), year_dir))?;
+    let path = dir.join(&entry.file);
+
+    let json =
+        fs::read_to_string(&path).map_err(|e| format!("Reading {}: {}", path.display(), e))?;
+    let mut data: TeamData =
+        serde_json::from_str(&json).map_err(|e| format!("Parsing {}: {}", path.display(), e))?;
+    data.depth_chart = chart.clone();
+
+    let json = serde_json::to_string_pretty(&data)
+        .map_err(|e| format!("Serializing {}: {}", team.name, e))?;
+    fs::write(&path, json).map_err(|e| format!("Writing {}: {}", path.display(), e))
+}
+
+fn read_manifest(dir: &Path) -> Result<LeagueManifest, String> {
     let manifest_path = dir.join(MANIFEST_FILE);
 
     let manifest_json = fs::read_to_string(&manifest_path).map_err(|e| {
@@ -151,6 +192,16 @@ pub fn load_league(year_dir: &str) -> Result<TeamList, String> {
             FORMAT_VERSION
         ));
     }
+    Ok(manifest)
+}
+
+/// Load a full [`TeamList`] from a year directory (e.g. `data/1983`).
+///
+/// Reads the manifest, then each referenced team file, and rebuilds the runtime
+/// model (including the per-position id lookup maps).
+pub fn load_league(year_dir: &str) -> Result<TeamList, String> {
+    let dir = Path::new(year_dir);
+    let manifest = read_manifest(dir)?;
 
     let mut rosters: Vec<Roster> = Vec::with_capacity(manifest.teams.len());
     for entry in &manifest.teams {
@@ -216,6 +267,58 @@ mod tests {
         let json = qb.get_json();
         assert!(json.get("quick").is_some(), "QB quick stats present");
 
+        // Every roster gets its team card, the two-team cities included.
+        assert!(reloaded.teams.values().all(|r| r.team_card().is_some()));
+        let raiders = reloaded
+            .get_team(&TeamID {
+                name: "L.A. Raiders".into(),
+                year: "1983".into(),
+            })
+            .and_then(|r| r.team_card())
+            .expect("Raiders team card");
+        assert_eq!((raiders.big_play_home, raiders.big_play_road), (4, 4));
+
+        let _ = fs::remove_dir_all(&out_root);
+    }
+
+    #[test]
+    fn test_depth_chart_is_saved_with_the_team() {
+        let out_root = std::env::temp_dir().join("spf_core_depth_chart_test");
+        let _ = fs::remove_dir_all(&out_root);
+        let out = out_root.to_str().unwrap();
+        let team = TeamID {
+            name: "N.Y. Giants".into(),
+            year: "1983".into(),
+        };
+        let league = TeamList::from_rosters(vec![Roster::from_players(team.clone(), vec![])]);
+        write_league(out, "1983", &league).expect("write_league");
+
+        let lineup = serde_json::from_value(serde_json::json!({
+            "qb": "QB-1", "b1": "RB-1", "b2": "RB-2", "le": "WR-1", "re": "TE-1",
+            "fl1": "WR-2", "lt": "OL-1", "lg": "OL-2", "c": "OL-3", "rg": "OL-4", "rt": "OL-5",
+        }))
+        .unwrap();
+        let mut chart = DepthChart::default();
+        chart.offense.insert("I-form".into(), lineup);
+        let year_dir = format!("{}/1983", out);
+        save_depth_chart(&year_dir, &team, &chart).expect("save_depth_chart");
+
+        let reloaded = load_league(&year_dir).expect("load_league");
+        let roster = reloaded.get_team(&team).expect("team reloads");
+        assert_eq!(
+            roster.depth_chart().offense.keys().collect::<Vec<_>>(),
+            vec!["I-form"]
+        );
+        assert!(save_depth_chart(
+            &year_dir,
+            &TeamID {
+                name: "Nobody".into(),
+                year: "1983".into()
+            },
+            &chart
+        )
+        .is_err());
+
         let _ = fs::remove_dir_all(&out_root);
     }
 }
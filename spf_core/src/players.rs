@@ -9,9 +9,10 @@ use strum_macros::Display;
 use utoipa::ToSchema;
 
 use crate::{
+    lineup::DepthChart,
     loader::{
-        load_dbs, load_dls, load_krs, load_ks, load_lbs, load_ols, load_qbs, load_rbs, load_tes,
-        load_wrs,
+        load_dbs, load_dls, load_krs, load_ks, load_lbs, load_ols, load_qbs, load_rbs, load_teams,
+        load_tes, load_wrs,
     },
     shiftable::{PassResult, PassRushResult},
     stats::{NumStat, Range, RangedStats, TripleStat, TwelveStats},
@@ -298,10 +299,11 @@ pub struct PRStats {
     pub returners: Vec<Returner>,
 }
 
+/// A team's card: the ratings that belong to the club rather than to any one player.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeamStats {
     pub team: TeamID,
-    pub position: Position,
+    /// How big a threat the offense is for a big play at home and on the road.
     pub big_play_home: i32,
     pub big_play_road: i32,
     pub fumbles_lost: Range,
@@ -474,6 +476,23 @@ impl PlayerUtils {
         }
     }
 
+    pub fn get_intercepts(player: &dyn BasePlayer) -> Option<Range> {
+        match player.get_full_player() {
+            Player::DB(db) => Some(db.intercepts),
+            Player::LB(lb) => Some(lb.intercepts),
+            _ => None,
+        }
+    }
+
+    /// The long-gain letter on a wide receiver's card. Only the exceptional receivers
+    /// carry one; everyone else (and every tight end) has it blank.
+    pub fn get_receiving_lg(player: &dyn BasePlayer) -> Option<char> {
+        match player.get_full_player() {
+            Player::WR(wr) if wr.lg.is_ascii_alphabetic() => Some(wr.lg),
+            _ => None,
+        }
+    }
+
     pub fn get_pass_block(player: &dyn BasePlayer) -> i32 {
         match player.get_full_player() {
             Player::OL(ol) => ol.pass_block,
@@ -549,6 +568,13 @@ pub struct Roster {
 
     #[serde(bound(serialize = "Vec<Box<dyn BasePlayer>>: Serialize"))]
     players: Vec<Box<dyn BasePlayer>>,
+
+    /// The team's named lineups; not part of the card data.
+    #[serde(skip)]
+    depth_chart: DepthChart,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    team_card: Option<TeamStats>,
 }
 
 impl Roster {
@@ -578,7 +604,12 @@ impl Roster {
             players.extend(k.into_iter().map(|s| Box::new(s) as Box<dyn BasePlayer>));
             players.extend(kr.into_iter().map(|s| Box::new(s) as Box<dyn BasePlayer>));
 
-            Self { players, team_name }
+            Self {
+                players,
+                team_name,
+                depth_chart: DepthChart::default(),
+                team_card: None,
+            }
         }
     }
 
@@ -589,9 +620,35 @@ impl Roster {
         Self {
             team_name,
             players: players.into_iter().map(|p| p.into_base_player()).collect(),
+            depth_chart: DepthChart::default(),
+            team_card: None,
         }
     }
 
+    pub fn with_team_card(self, team_card: Option<TeamStats>) -> Self {
+        Self { team_card, ..self }
+    }
+
+    /// The team card, for rosters whose data carries one.
+    pub fn team_card(&self) -> Option<&TeamStats> {
+        self.team_card.as_ref()
+    }
+
+    pub fn with_depth_chart(self, depth_chart: DepthChart) -> Self {
+        Self {
+            depth_chart,
+            ..self
+        }
+    }
+
+    pub fn depth_chart(&self) -> &DepthChart {
+        &self.depth_chart
+    }
+
+    pub fn depth_chart_mut(&mut self) -> &mut DepthChart {
+        &mut self.depth_chart
+    }
+
     pub fn get_team_name(&self) -> &TeamID {
         &self.team_name
     }
@@ -704,6 +761,10 @@ impl TeamList {
         let (dbs, all_dbs) = TeamList::disperse_players(load_dbs(format!("{}/83DB.txt", dir)));
         let (ks, all_ks) = TeamList::disperse_players(load_ks(format!("{}/83K.txt", dir)));
         let (krs, all_krs) = TeamList::disperse_players(load_krs(format!("{}/83KR.txt", dir)));
+        let mut team_cards: HashMap<TeamID, TeamStats> = load_teams(format!("{}/83Team.txt", dir))
+            .into_iter()
+            .map(|card| (card.team.clone(), card))
+            .collect();
 
         let mut teams: HashMap<TeamID, Roster> = HashMap::new();
         for t in qbs.keys() {
@@ -723,7 +784,8 @@ impl TeamList {
                     ols.get(t).unwrap().to_vec(),
                     ks.get(t).unwrap().to_vec(),
                     krs.get(t).unwrap().to_vec(),
-                ),
+                )
+                .with_team_card(team_cards.remove(t)),
             );
         }
 
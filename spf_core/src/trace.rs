@@ -0,0 +1,26 @@
+//! The engine's running commentary ("Will Run Play", the ranges a roll is read against, ...),
+//! printed to stdout for whoever is watching the server. Headless tools whose stdout is the
+//! report turn it off with [`set_quiet`].
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Turn the commentary off (or back on) for the whole process.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// `println!`, unless [`set_quiet`] has turned the commentary off.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if !$crate::trace::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}